@@ -1,43 +1,69 @@
-use std::str::Chars;
+use std::{iter::FusedIterator, str::Chars};
 
 use unicode_categories::UnicodeCategories;
 
-use super::types::{DelimiterK, DigitK, IdentifierK, OperatorK, Token, TokenK, UnknownK};
+use super::types::{DelimiterK, DigitK, IdentifierK, LiteralK, OperatorK, Token, TokenK, UnknownK};
 
 #[derive(Debug, Clone)]
 pub struct Cursor<'a> {
     length: usize,
     source: &'a str,
     chars: Chars<'a>,
+    finished: bool,
+    shebang: bool,
 }
 
 const EOF_CHAR: char = '\0';
 
+fn is_separated(digits: &str, radix: u32) -> bool {
+    !digits.is_empty()
+        && !digits.starts_with('_')
+        && !digits.ends_with('_')
+        && !digits.contains("__")
+        && digits.chars().all(|c| c == '_' || c.is_digit(radix))
+}
+
+// Operators are runs of Unicode symbols (Sm, Sc, Sk, So) and punctuation
+// (Pc, Pd, Ps, Pe, Pi, Pf, Po), minus the delimiters and quotes which
+// always form tokens of their own.
+fn is_operator_char(c: char) -> bool {
+    !"(){}[]\"'".contains(c) && (c.is_symbol() || c.is_punctuation())
+}
+
 impl<'a> Cursor<'a> {
     pub fn new(source: &'a str) -> Self {
         Self {
             length: source.len(),
             source,
             chars: source.chars(),
+            finished: false,
+            shebang: false,
         }
     }
 
+    pub fn skipping_shebang(mut self) -> Self {
+        self.shebang = true;
+        self
+    }
+
     fn is_eof(&self) -> bool {
         self.chars.as_str().is_empty()
     }
 
-    fn consumed(&self) -> usize {
+    pub(crate) fn consumed(&self) -> usize {
         self.length - self.chars.as_str().len()
     }
 
+    fn peek_nth(&mut self, n: usize) -> char {
+        self.chars.clone().nth(n).unwrap_or(EOF_CHAR)
+    }
+
     fn peek_1(&mut self) -> char {
-        self.chars.clone().next().unwrap_or(EOF_CHAR)
+        self.peek_nth(0)
     }
 
     fn peek_2(&mut self) -> char {
-        let mut chars = self.chars.clone();
-        chars.next();
-        chars.next().unwrap_or(EOF_CHAR)
+        self.peek_nth(1)
     }
 
     fn take(&mut self) -> char {
@@ -52,6 +78,64 @@ impl<'a> Cursor<'a> {
 }
 
 impl<'a> Cursor<'a> {
+    pub(crate) fn take_block_comment(&mut self) -> bool {
+        let mut depth = 0;
+        loop {
+            match (self.peek_1(), self.peek_2()) {
+                ('{', '-') => {
+                    self.take();
+                    self.take();
+                    depth += 1;
+                }
+                ('-', '}') => {
+                    self.take();
+                    self.take();
+                    depth -= 1;
+                    if depth == 0 {
+                        break true;
+                    }
+                }
+                _ if self.is_eof() => break false,
+                _ => {
+                    self.take();
+                }
+            }
+        }
+    }
+
+    fn take_escape(&mut self) -> Result<(), UnknownK> {
+        match self.peek_1() {
+            '0' | 'n' | 'r' | 't' | '\\' | '\'' | '"' => {
+                self.take();
+                Ok(())
+            }
+            'u' => {
+                self.take();
+                if self.peek_1() != '{' {
+                    return Err(UnknownK::InvalidUnicodeEscape);
+                }
+                self.take();
+                let begin = self.consumed();
+                self.take_while(|c| c.is_ascii_hexdigit());
+                let end = self.consumed();
+                if self.peek_1() != '}' {
+                    return Err(UnknownK::InvalidUnicodeEscape);
+                }
+                self.take();
+                match u32::from_str_radix(&self.source[begin..end], 16) {
+                    Ok(value) if end - begin <= 6 && char::from_u32(value).is_some() => Ok(()),
+                    _ => Err(UnknownK::InvalidUnicodeEscape),
+                }
+            }
+            '\n' => Err(UnknownK::InvalidEscape),
+            _ if self.is_eof() => Err(UnknownK::InvalidEscape),
+            _ => {
+                self.take();
+                Err(UnknownK::InvalidEscape)
+            }
+        }
+    }
+
     pub fn take_token(&mut self) -> Token {
         let comment_begin = self.consumed();
         loop {
@@ -59,17 +143,23 @@ impl<'a> Cursor<'a> {
                 ('-', '-') => {
                     self.take_while(|c| c != '\n');
                 }
-                ('{', '-') => loop {
-                    if self.is_eof() {
-                        break;
-                    } else if self.peek_1() == '-' && self.peek_2() == '}' {
-                        self.take();
-                        self.take();
-                        break;
-                    } else {
-                        self.take();
+                ('#', '!') if self.shebang && self.consumed() == 0 => {
+                    self.take_while(|c| c != '\n');
+                }
+                ('{', '-') => {
+                    let begin = self.consumed();
+                    if !self.take_block_comment() {
+                        let end = self.consumed();
+                        return Token {
+                            comment_begin,
+                            comment_end: begin,
+                            begin,
+                            end,
+                            kind: TokenK::Unknown(UnknownK::UnfinishedComment),
+                            depth: 0,
+                        };
                     }
-                },
+                }
                 (i, _) if i.is_whitespace() => {
                     self.take_while(|c| c.is_whitespace());
                 }
@@ -100,6 +190,8 @@ impl<'a> Cursor<'a> {
                     "case" => IdentifierK::Case,
                     "do" => IdentifierK::Do,
                     "else" => IdentifierK::Else,
+                    "false" => IdentifierK::False,
+                    "forall" => IdentifierK::Forall,
                     "if" => IdentifierK::If,
                     "in" => IdentifierK::In,
                     "infixl" => IdentifierK::Infixl,
@@ -107,21 +199,114 @@ impl<'a> Cursor<'a> {
                     "let" => IdentifierK::Let,
                     "of" => IdentifierK::Of,
                     "then" => IdentifierK::Then,
+                    "true" => IdentifierK::True,
                     "type" => IdentifierK::Type,
                     _ => IdentifierK::Lower,
                 })
             }
             initial if initial.is_letter_uppercase() => {
                 self.take_while(|c| c.is_letter() || c.is_number() || "'_".contains(c));
-                TokenK::Identifier(IdentifierK::Upper)
+                // Upper-case names directly followed by a period and another
+                // name form a module qualifier, like in `Data.List.map`. Any
+                // whitespace around the period leaves it as an operator.
+                let mut kind = IdentifierK::Upper;
+                while self.peek_1() == '.' {
+                    let next = self.peek_2();
+                    if next.is_letter_uppercase() {
+                        kind = IdentifierK::QualifiedUpper;
+                    } else if next.is_letter_lowercase() {
+                        kind = IdentifierK::QualifiedLower;
+                    } else {
+                        break;
+                    }
+                    self.take();
+                    self.take_while(|c| c.is_letter() || c.is_number() || "'_".contains(c));
+                    if kind == IdentifierK::QualifiedLower {
+                        break;
+                    }
+                }
+                TokenK::Identifier(kind)
+            }
+            // Literals
+            '\'' => {
+                let mut escaped = Ok(());
+                match self.peek_1() {
+                    '\\' => {
+                        self.take();
+                        escaped = self.take_escape();
+                    }
+                    '\n' => (),
+                    _ if self.is_eof() => (),
+                    _ => {
+                        self.take();
+                    }
+                }
+                // ''
+                if self.consumed() - begin == 2 && self.source[begin..].starts_with("''") {
+                    TokenK::Unknown(UnknownK::UnfinishedCharacter)
+                // 'a'
+                } else if self.consumed() - begin > 1 && self.peek_1() == '\'' {
+                    self.take();
+                    match escaped {
+                        Ok(()) => TokenK::Literal(LiteralK::Character),
+                        Err(kind) => TokenK::Unknown(kind),
+                    }
+                // 'a
+                } else {
+                    TokenK::Unknown(UnknownK::UnfinishedCharacter)
+                }
+            }
+            '"' if self.peek_1() == '"' && self.peek_2() == '"' => {
+                self.take();
+                self.take();
+                loop {
+                    if self.source[self.consumed()..].starts_with("\"\"\"") {
+                        self.take();
+                        self.take();
+                        self.take();
+                        break TokenK::Literal(LiteralK::MultilineString);
+                    } else if self.is_eof() {
+                        break TokenK::Unknown(UnknownK::UnfinishedString);
+                    } else {
+                        self.take();
+                    }
+                }
+            }
+            '"' => {
+                let mut escaped = Ok(());
+                loop {
+                    match self.peek_1() {
+                        '"' => {
+                            self.take();
+                            match escaped {
+                                Ok(()) => break TokenK::Literal(LiteralK::String),
+                                Err(kind) => break TokenK::Unknown(kind),
+                            }
+                        }
+                        '\\' => {
+                            self.take();
+                            escaped = escaped.and(self.take_escape());
+                        }
+                        '\n' => break TokenK::Unknown(UnknownK::UnfinishedString),
+                        _ if self.is_eof() => break TokenK::Unknown(UnknownK::UnfinishedString),
+                        _ => {
+                            self.take();
+                        }
+                    }
+                }
             }
             // Compound Symbols
             '_' => TokenK::Operator(OperatorK::Underscore),
-            initial if initial.is_symbol() || initial.is_punctuation() => {
-                self.take_while(|c| !"(){}[]".contains(c) && (c.is_symbol() || c.is_punctuation()));
+            initial if is_operator_char(initial) => {
+                self.take_while(is_operator_char);
                 let end = self.consumed();
                 TokenK::Operator(match &self.source[begin..end] {
+                    // A minus sign directly attached to a digit is kept apart from
+                    // other operators, such that the parser can decide between a
+                    // negative literal or subtraction: `f -3` against `a - 3`.
+                    "-" if self.peek_1().is_ascii_digit() => OperatorK::Minus,
                     "->" => OperatorK::ArrowRight,
+                    "=>" => OperatorK::ArrowConstraint,
                     "<-" => OperatorK::ArrowLeft,
                     "=" => OperatorK::Equal,
                     ":" => OperatorK::Colon,
@@ -135,28 +320,64 @@ impl<'a> Cursor<'a> {
                 })
             }
             // Digits
+            '0' if "xob".contains(self.peek_1()) => {
+                let radix = match self.take() {
+                    'x' => 16,
+                    'o' => 8,
+                    _ => 2,
+                };
+                self.take_while(|c| c.is_alphanumeric() || c == '_');
+                if is_separated(&self.source[begin + 2..self.consumed()], radix) {
+                    TokenK::Digit(DigitK::Int)
+                } else {
+                    TokenK::Unknown(UnknownK::MalformedInteger)
+                }
+            }
             initial if initial.is_ascii_digit() => {
-                self.take_while(|c| c.is_ascii_digit());
-                if self.peek_1() == '.' {
-                    // 1..
-                    if self.peek_2() == '.' {
-                        TokenK::Digit(DigitK::Int)
+                self.take_while(|c| c.is_ascii_digit() || c == '_');
+                let mut kind = TokenK::Digit(DigitK::Int);
+                // 1.. is left for the operator
+                if self.peek_1() == '.' && self.peek_2() != '.' {
                     // 1.2
-                    } else if self.peek_2().is_ascii_digit() {
+                    if self.peek_2().is_ascii_digit() {
                         self.take();
-                        self.take_while(|c| c.is_ascii_digit());
-                        TokenK::Digit(DigitK::Float)
+                        self.take_while(|c| c.is_ascii_digit() || c == '_');
+                        kind = TokenK::Digit(DigitK::Float);
                     // 1.
                     } else {
                         self.take();
-                        TokenK::Unknown(UnknownK::UnfinishedFloat)
+                        kind = TokenK::Unknown(UnknownK::UnfinishedFloat);
                     }
-                } else {
-                    TokenK::Digit(DigitK::Int)
+                }
+                if let ('e' | 'E', TokenK::Digit(_)) = (self.peek_1(), kind) {
+                    self.take();
+                    if let '+' | '-' = self.peek_1() {
+                        self.take();
+                    }
+                    // 1e10
+                    if self.peek_1().is_ascii_digit() {
+                        self.take_while(|c| c.is_ascii_digit() || c == '_');
+                        kind = TokenK::Digit(DigitK::Float);
+                    // 1e
+                    } else {
+                        kind = TokenK::Unknown(UnknownK::UnfinishedFloat);
+                    }
+                }
+                let separated = self.source[begin..self.consumed()]
+                    .split(|c| ".eE+-".contains(c))
+                    .all(|digits| digits.is_empty() || is_separated(digits, 10));
+                match kind {
+                    TokenK::Digit(DigitK::Int) if !separated => {
+                        TokenK::Unknown(UnknownK::MalformedInteger)
+                    }
+                    TokenK::Digit(DigitK::Float) if !separated => {
+                        TokenK::Unknown(UnknownK::MalformedFloat)
+                    }
+                    _ => kind,
                 }
             }
             // End of file
-            '\0' => TokenK::Unknown(UnknownK::EndOfFile),
+            '\0' if self.consumed() == begin => TokenK::Unknown(UnknownK::EndOfFile),
             // Unknown Token
             _ => TokenK::Unknown(UnknownK::UnknownToken),
         };
@@ -173,13 +394,40 @@ impl<'a> Cursor<'a> {
     }
 }
 
+// Yields every token up to and including the end of file, and nothing
+// after that. Unknown tokens always consume input, so errors never stall.
+impl<'a> Iterator for Cursor<'a> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        if self.finished {
+            return None;
+        }
+        let token = self.take_token();
+        self.finished = token.is_eof();
+        Some(token)
+    }
+}
+
+impl<'a> FusedIterator for Cursor<'a> {}
+
 #[cfg(test)]
 mod tests {
-    use crate::types::UnknownK;
+    use crate::types::{LiteralK, UnknownK};
 
-    use super::{Cursor, DigitK, IdentifierK, OperatorK, Token, TokenK};
+    use super::{is_operator_char, Cursor, DigitK, IdentifierK, OperatorK, Token, TokenK};
     use pretty_assertions::assert_eq;
 
+    fn kinds(source: &str) -> Vec<TokenK> {
+        Cursor::new(source).map(|token| token.kind).collect()
+    }
+
+    fn spans(source: &str) -> Vec<(usize, usize, TokenK)> {
+        Cursor::new(source)
+            .map(|token| (token.begin, token.end, token.kind))
+            .collect()
+    }
+
     #[test]
     fn double_period_after_int() {
         let source = "1..2";
@@ -296,4 +544,900 @@ mod tests {
             }
         )
     }
+
+    #[test]
+    fn string_with_escaped_quote() {
+        let source = r#""a\"b""#;
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 6,
+                kind: TokenK::Literal(LiteralK::String),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 6,
+                comment_end: 6,
+                begin: 6,
+                end: 6,
+                kind: TokenK::Unknown(UnknownK::EndOfFile),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn string_after_operator() {
+        let source = r#"<>"a""#;
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 2,
+                kind: TokenK::Operator(OperatorK::Source),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 2,
+                comment_end: 2,
+                begin: 2,
+                end: 5,
+                kind: TokenK::Literal(LiteralK::String),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn unfinished_string() {
+        let source = "\"abc\nd";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 4,
+                kind: TokenK::Unknown(UnknownK::UnfinishedString),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 4,
+                comment_end: 5,
+                begin: 5,
+                end: 6,
+                kind: TokenK::Identifier(IdentifierK::Lower),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn character_literals() {
+        let source = r"'a' '\n' '\''";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 3,
+                kind: TokenK::Literal(LiteralK::Character),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 3,
+                comment_end: 4,
+                begin: 4,
+                end: 8,
+                kind: TokenK::Literal(LiteralK::Character),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 8,
+                comment_end: 9,
+                begin: 9,
+                end: 13,
+                kind: TokenK::Literal(LiteralK::Character),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn unfinished_character() {
+        let source = "'' 'a";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 2,
+                kind: TokenK::Unknown(UnknownK::UnfinishedCharacter),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 2,
+                comment_end: 3,
+                begin: 3,
+                end: 5,
+                kind: TokenK::Unknown(UnknownK::UnfinishedCharacter),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 5,
+                comment_end: 5,
+                begin: 5,
+                end: 5,
+                kind: TokenK::Unknown(UnknownK::EndOfFile),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn nested_block_comment() {
+        let source = "{- outer {- inner -} -} 1";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 24,
+                begin: 24,
+                end: 25,
+                kind: TokenK::Digit(DigitK::Int),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn unfinished_block_comment() {
+        let source = "1 {- outer {- inner -}";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 1,
+                kind: TokenK::Digit(DigitK::Int),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 1,
+                comment_end: 2,
+                begin: 2,
+                end: 22,
+                kind: TokenK::Unknown(UnknownK::UnfinishedComment),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 22,
+                comment_end: 22,
+                begin: 22,
+                end: 22,
+                kind: TokenK::Unknown(UnknownK::EndOfFile),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn radix_integers() {
+        let source = "0x1F 0o17 0b1010";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 4,
+                kind: TokenK::Digit(DigitK::Int),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 4,
+                comment_end: 5,
+                begin: 5,
+                end: 9,
+                kind: TokenK::Digit(DigitK::Int),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 9,
+                comment_end: 10,
+                begin: 10,
+                end: 16,
+                kind: TokenK::Digit(DigitK::Int),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn malformed_radix_integers() {
+        let source = "0x1G 0b12 0o";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 4,
+                kind: TokenK::Unknown(UnknownK::MalformedInteger),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 4,
+                comment_end: 5,
+                begin: 5,
+                end: 9,
+                kind: TokenK::Unknown(UnknownK::MalformedInteger),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 9,
+                comment_end: 10,
+                begin: 10,
+                end: 12,
+                kind: TokenK::Unknown(UnknownK::MalformedInteger),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn float_exponents() {
+        let source = "1.5e10 2e-3 6.022E+23 1e0";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 6,
+                kind: TokenK::Digit(DigitK::Float),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 6,
+                comment_end: 7,
+                begin: 7,
+                end: 11,
+                kind: TokenK::Digit(DigitK::Float),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 11,
+                comment_end: 12,
+                begin: 12,
+                end: 21,
+                kind: TokenK::Digit(DigitK::Float),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 21,
+                comment_end: 22,
+                begin: 22,
+                end: 25,
+                kind: TokenK::Digit(DigitK::Float),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn unfinished_float_exponent() {
+        let source = "1e 1.0e-";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 2,
+                kind: TokenK::Unknown(UnknownK::UnfinishedFloat),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 2,
+                comment_end: 3,
+                begin: 3,
+                end: 8,
+                kind: TokenK::Unknown(UnknownK::UnfinishedFloat),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn digit_separators() {
+        let source = "1_000_000 3.141_592 0xFF_FF";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 9,
+                kind: TokenK::Digit(DigitK::Int),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 9,
+                comment_end: 10,
+                begin: 10,
+                end: 19,
+                kind: TokenK::Digit(DigitK::Float),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 19,
+                comment_end: 20,
+                begin: 20,
+                end: 27,
+                kind: TokenK::Digit(DigitK::Int),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn malformed_digit_separators() {
+        let source = "123_ 1__2 1.5_ 0x_1 _1";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 4,
+                kind: TokenK::Unknown(UnknownK::MalformedInteger),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 4,
+                comment_end: 5,
+                begin: 5,
+                end: 9,
+                kind: TokenK::Unknown(UnknownK::MalformedInteger),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 9,
+                comment_end: 10,
+                begin: 10,
+                end: 14,
+                kind: TokenK::Unknown(UnknownK::MalformedFloat),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 14,
+                comment_end: 15,
+                begin: 15,
+                end: 19,
+                kind: TokenK::Unknown(UnknownK::MalformedInteger),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 19,
+                comment_end: 20,
+                begin: 20,
+                end: 21,
+                kind: TokenK::Operator(OperatorK::Underscore),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 21,
+                comment_end: 21,
+                begin: 21,
+                end: 22,
+                kind: TokenK::Digit(DigitK::Int),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn fused_after_end_of_file() {
+        let source = "1. ' \"";
+        let mut cursor = Cursor::new(source);
+        let kinds: Vec<TokenK> = cursor.by_ref().map(|token| token.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenK::Unknown(UnknownK::UnfinishedFloat),
+                TokenK::Unknown(UnknownK::UnfinishedCharacter),
+                TokenK::Unknown(UnknownK::UnfinishedString),
+                TokenK::Unknown(UnknownK::EndOfFile),
+            ]
+        );
+        assert_eq!(cursor.next(), None);
+        assert_eq!(cursor.next(), None);
+    }
+
+    #[test]
+    fn trivia_only_sources() {
+        for source in [
+            "",
+            "   ",
+            "\n\n",
+            "-- comment",
+            "{- comment -}",
+            " -- a\n{- b -}\n",
+        ] {
+            let tokens: Vec<Token> = Cursor::new(source).collect();
+            assert_eq!(
+                tokens,
+                vec![Token {
+                    comment_begin: 0,
+                    comment_end: source.len(),
+                    begin: source.len(),
+                    end: source.len(),
+                    kind: TokenK::Unknown(UnknownK::EndOfFile),
+                    depth: 0,
+                }],
+                "{:?}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn tokens_tile_invalid_input() {
+        let source = "\u{1}\0 '' 1e 0x_ \"\\q\n\u{7f}{- \u{3}";
+        let tokens: Vec<Token> = Cursor::new(source).collect();
+        assert!(tokens[..tokens.len() - 1]
+            .iter()
+            .all(|token| matches!(token.kind, TokenK::Unknown(_)) && token.begin < token.end));
+        let mut offset = 0;
+        for token in &tokens {
+            assert_eq!(token.comment_begin, offset);
+            offset = token.end;
+        }
+        assert_eq!(offset, source.len());
+        assert!(tokens.last().unwrap().is_eof());
+    }
+
+    #[test]
+    fn iterate_by_value() {
+        let mut count = 0;
+        for token in Cursor::new("main = 1") {
+            count += 1;
+            if count == 4 {
+                assert!(token.is_eof());
+            }
+        }
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn recover_after_unknown_tokens() {
+        let source = "a \u{7} b \0 c";
+        assert_eq!(
+            kinds(source),
+            vec![
+                TokenK::Identifier(IdentifierK::Lower),
+                TokenK::Unknown(UnknownK::UnknownToken),
+                TokenK::Identifier(IdentifierK::Lower),
+                TokenK::Unknown(UnknownK::UnknownToken),
+                TokenK::Identifier(IdentifierK::Lower),
+                TokenK::Unknown(UnknownK::EndOfFile),
+            ]
+        );
+    }
+
+    #[test]
+    fn keywords_and_identifiers() {
+        let source = "let lettuce in inner";
+        let tokens: Vec<Token> = Cursor::new(source).collect();
+        assert_eq!(
+            tokens.iter().map(|token| token.kind).collect::<Vec<_>>(),
+            vec![
+                TokenK::Identifier(IdentifierK::Let),
+                TokenK::Identifier(IdentifierK::Lower),
+                TokenK::Identifier(IdentifierK::In),
+                TokenK::Identifier(IdentifierK::Lower),
+                TokenK::Unknown(UnknownK::EndOfFile),
+            ]
+        );
+        assert_eq!(
+            tokens.iter().map(Token::is_keyword).collect::<Vec<_>>(),
+            vec![true, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn minus_sign_before_digit() {
+        assert_eq!(
+            kinds("-3"),
+            vec![
+                TokenK::Operator(OperatorK::Minus),
+                TokenK::Digit(DigitK::Int),
+                TokenK::Unknown(UnknownK::EndOfFile),
+            ]
+        );
+        assert_eq!(
+            kinds("a - 3"),
+            vec![
+                TokenK::Identifier(IdentifierK::Lower),
+                TokenK::Operator(OperatorK::Source),
+                TokenK::Digit(DigitK::Int),
+                TokenK::Unknown(UnknownK::EndOfFile),
+            ]
+        );
+        assert_eq!(
+            kinds("f -3"),
+            vec![
+                TokenK::Identifier(IdentifierK::Lower),
+                TokenK::Operator(OperatorK::Minus),
+                TokenK::Digit(DigitK::Int),
+                TokenK::Unknown(UnknownK::EndOfFile),
+            ]
+        );
+    }
+
+    #[test]
+    fn multiline_string() {
+        let source = "\"\"\"\n  a\n  \"b\"\n\"\"\" \"\"";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 17,
+                kind: TokenK::Literal(LiteralK::MultilineString),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 17,
+                comment_end: 18,
+                begin: 18,
+                end: 20,
+                kind: TokenK::Literal(LiteralK::String),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn unfinished_multiline_string() {
+        let source = "\"\"\"\na\"\"";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 0,
+                begin: 0,
+                end: 7,
+                kind: TokenK::Unknown(UnknownK::UnfinishedString),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 7,
+                comment_end: 7,
+                begin: 7,
+                end: 7,
+                kind: TokenK::Unknown(UnknownK::EndOfFile),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn deeply_nested_block_comment() {
+        let source = "{- a {- b {- c -} -} d -} 1 {- e {- f {- g -} -}";
+        let mut cursor = Cursor::new(source);
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 0,
+                comment_end: 26,
+                begin: 26,
+                end: 27,
+                kind: TokenK::Digit(DigitK::Int),
+                depth: 0,
+            }
+        );
+        assert_eq!(
+            cursor.take_token(),
+            Token {
+                comment_begin: 27,
+                comment_end: 28,
+                begin: 28,
+                end: 48,
+                kind: TokenK::Unknown(UnknownK::UnfinishedComment),
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn character_escapes_and_overlong_characters() {
+        assert_eq!(
+            kinds(r"'\\' '\'' '\t'"),
+            vec![
+                TokenK::Literal(LiteralK::Character),
+                TokenK::Literal(LiteralK::Character),
+                TokenK::Literal(LiteralK::Character),
+                TokenK::Unknown(UnknownK::EndOfFile),
+            ]
+        );
+        assert_eq!(
+            kinds("'ab'"),
+            vec![
+                TokenK::Unknown(UnknownK::UnfinishedCharacter),
+                TokenK::Identifier(IdentifierK::Lower),
+                TokenK::Unknown(UnknownK::EndOfFile),
+            ]
+        );
+    }
+
+    #[test]
+    fn string_escapes() {
+        assert_eq!(
+            kinds(r#""a\"b" "\n\t\\\0" "\q" '\q'"#),
+            vec![
+                TokenK::Literal(LiteralK::String),
+                TokenK::Literal(LiteralK::String),
+                TokenK::Unknown(UnknownK::InvalidEscape),
+                TokenK::Unknown(UnknownK::InvalidEscape),
+                TokenK::Unknown(UnknownK::EndOfFile),
+            ]
+        );
+    }
+
+    #[test]
+    fn boolean_keywords() {
+        assert_eq!(
+            kinds("true false truer False"),
+            vec![
+                TokenK::Identifier(IdentifierK::True),
+                TokenK::Identifier(IdentifierK::False),
+                TokenK::Identifier(IdentifierK::Lower),
+                TokenK::Identifier(IdentifierK::Upper),
+                TokenK::Unknown(UnknownK::EndOfFile),
+            ]
+        );
+    }
+
+    #[test]
+    fn qualified_names() {
+        let source =
+            "List.map Data.Map.Internal.empty Data.Maybe.Just a.b a . b List . map Effect.";
+        let tokens: Vec<(TokenK, &str)> = Cursor::new(source)
+            .map(|token| (token.kind, &source[token.begin..token.end]))
+            .collect();
+        assert_eq!(
+            tokens,
+            vec![
+                (TokenK::Identifier(IdentifierK::QualifiedLower), "List.map"),
+                (
+                    TokenK::Identifier(IdentifierK::QualifiedLower),
+                    "Data.Map.Internal.empty"
+                ),
+                (
+                    TokenK::Identifier(IdentifierK::QualifiedUpper),
+                    "Data.Maybe.Just"
+                ),
+                (TokenK::Identifier(IdentifierK::Lower), "a"),
+                (TokenK::Operator(OperatorK::Period), "."),
+                (TokenK::Identifier(IdentifierK::Lower), "b"),
+                (TokenK::Identifier(IdentifierK::Lower), "a"),
+                (TokenK::Operator(OperatorK::Period), "."),
+                (TokenK::Identifier(IdentifierK::Lower), "b"),
+                (TokenK::Identifier(IdentifierK::Upper), "List"),
+                (TokenK::Operator(OperatorK::Period), "."),
+                (TokenK::Identifier(IdentifierK::Lower), "map"),
+                (TokenK::Identifier(IdentifierK::Upper), "Effect"),
+                (TokenK::Operator(OperatorK::Period), "."),
+                (TokenK::Unknown(UnknownK::EndOfFile), ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn reserved_operators() {
+        // Reserved operators are only recognized when they make up the
+        // whole run of operator characters.
+        assert_eq!(
+            kinds("| || |> = == : :: . .. ? ?? ! != < <= > >="),
+            vec![
+                TokenK::Operator(OperatorK::Pipe),
+                TokenK::Operator(OperatorK::Source),
+                TokenK::Operator(OperatorK::Source),
+                TokenK::Operator(OperatorK::Equal),
+                TokenK::Operator(OperatorK::Source),
+                TokenK::Operator(OperatorK::Colon),
+                TokenK::Operator(OperatorK::Source),
+                TokenK::Operator(OperatorK::Period),
+                TokenK::Operator(OperatorK::Source),
+                TokenK::Operator(OperatorK::Question),
+                TokenK::Operator(OperatorK::Source),
+                TokenK::Operator(OperatorK::Bang),
+                TokenK::Operator(OperatorK::Source),
+                TokenK::Operator(OperatorK::LessThan),
+                TokenK::Operator(OperatorK::Source),
+                TokenK::Operator(OperatorK::GreaterThan),
+                TokenK::Operator(OperatorK::Source),
+                TokenK::Unknown(UnknownK::EndOfFile),
+            ]
+        );
+    }
+
+    #[test]
+    fn type_keywords() {
+        assert_eq!(
+            kinds("forall foralls => ==> =>>"),
+            vec![
+                TokenK::Identifier(IdentifierK::Forall),
+                TokenK::Identifier(IdentifierK::Lower),
+                TokenK::Operator(OperatorK::ArrowConstraint),
+                TokenK::Operator(OperatorK::Source),
+                TokenK::Operator(OperatorK::Source),
+                TokenK::Unknown(UnknownK::EndOfFile),
+            ]
+        );
+    }
+
+    #[test]
+    fn operator_characters() {
+        assert!("+-*/<>=!?|&.:$%^~@#,\\".chars().all(is_operator_char));
+        assert!("∘∀→≤⊕×".chars().all(is_operator_char));
+        assert!(!"(){}[]\"'".chars().any(is_operator_char));
+        assert!(!"aZ0 \t\nλ".chars().any(is_operator_char));
+    }
+
+    #[test]
+    fn unicode_escapes() {
+        assert_eq!(
+            kinds(r#""\u{1F600}" '\u{41}' "\u{}" "\u{110000}" "\u{1234567}" "\u41" "\n\u{D800}""#),
+            vec![
+                TokenK::Literal(LiteralK::String),
+                TokenK::Literal(LiteralK::Character),
+                TokenK::Unknown(UnknownK::InvalidUnicodeEscape),
+                TokenK::Unknown(UnknownK::InvalidUnicodeEscape),
+                TokenK::Unknown(UnknownK::InvalidUnicodeEscape),
+                TokenK::Unknown(UnknownK::InvalidUnicodeEscape),
+                TokenK::Unknown(UnknownK::InvalidUnicodeEscape),
+                TokenK::Unknown(UnknownK::EndOfFile),
+            ]
+        );
+    }
+
+    #[test]
+    fn peek_beyond_two_characters() {
+        let mut cursor = Cursor::new("abc");
+        assert_eq!(cursor.peek_1(), 'a');
+        assert_eq!(cursor.peek_2(), 'b');
+        assert_eq!(cursor.peek_nth(2), 'c');
+        assert_eq!(cursor.peek_nth(3), '\0');
+        assert_eq!(cursor.peek_nth(8), '\0');
+        Cursor::take(&mut cursor);
+        assert_eq!(cursor.peek_nth(1), 'c');
+        assert_eq!(cursor.peek_nth(2), '\0');
+    }
+
+    #[test]
+    fn period_runs_and_field_access() {
+        assert_eq!(
+            spans(". .. ..."),
+            vec![
+                (0, 1, TokenK::Operator(OperatorK::Period)),
+                (2, 4, TokenK::Operator(OperatorK::Source)),
+                (5, 8, TokenK::Operator(OperatorK::Source)),
+                (8, 8, TokenK::Unknown(UnknownK::EndOfFile)),
+            ]
+        );
+        assert_eq!(
+            spans("r.field"),
+            vec![
+                (0, 1, TokenK::Identifier(IdentifierK::Lower)),
+                (1, 2, TokenK::Operator(OperatorK::Period)),
+                (2, 7, TokenK::Identifier(IdentifierK::Lower)),
+                (7, 7, TokenK::Unknown(UnknownK::EndOfFile)),
+            ]
+        );
+    }
 }
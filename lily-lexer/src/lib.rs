@@ -1,22 +1,25 @@
-use self::{cursor::Cursor, layout::LayoutEngine, types::Token};
+use self::{
+    cursor::Cursor,
+    layout::LayoutEngine,
+    line_index::LineIndex,
+    types::{CommentK, LocatedError, Token, TokenK},
+};
 
 mod cursor;
 mod layout;
+pub mod line_index;
+pub mod literal;
 pub mod types;
 
 pub fn lex(source: &str) -> Vec<Token> {
-    let tokens = {
-        let mut cursor = Cursor::new(source);
-        let mut tokens = vec![];
-        loop {
-            let token = cursor.take_token();
-            tokens.push(token);
-            if token.is_eof() {
-                break tokens;
-            }
-        }
-    };
+    layout(source, Cursor::new(source).collect())
+}
 
+pub fn lex_skipping_shebang(source: &str) -> Vec<Token> {
+    layout(source, Cursor::new(source).skipping_shebang().collect())
+}
+
+fn layout(source: &str, tokens: Vec<Token>) -> Vec<Token> {
     if let [token] = &tokens[..] {
         if token.is_eof() {
             return tokens;
@@ -26,24 +29,9 @@ pub fn lex(source: &str) -> Vec<Token> {
     let tokens = {
         let input_tokens = tokens;
 
-        let get_position = |offset| {
-            let mut line = 1;
-            let mut column = 1;
-
-            for (current, character) in source.chars().enumerate() {
-                if current == offset {
-                    break;
-                }
-                if character == '\n' {
-                    column = 1;
-                    line += 1
-                } else {
-                    column += 1;
-                }
-            }
-
-            types::Position { line, column }
-        };
+        let index = LineIndex::new(source);
+        let mut positions = index.positions();
+        let mut get_position = |offset| positions.get(offset);
 
         let initial_position = if let Some(token) = input_tokens.first() {
             get_position(token.begin)
@@ -76,3 +64,276 @@ pub fn lex(source: &str) -> Vec<Token> {
 
     tokens
 }
+
+pub fn lex_with_text(source: &str) -> impl Iterator<Item = (Token, &str)> {
+    lex(source)
+        .into_iter()
+        .map(move |token| (token, &source[token.begin..token.end]))
+}
+
+pub fn lex_with_trivia(source: &str) -> impl Iterator<Item = (Token, &str)> {
+    lex(source)
+        .into_iter()
+        .map(move |token| (token, &source[token.comment_begin..token.comment_end]))
+}
+
+pub fn dump(source: &str) -> String {
+    let index = LineIndex::new(source);
+    lex(source)
+        .iter()
+        .map(|token| {
+            let (begin, end) = index.span(token);
+            format!(
+                "{}:{}-{}:{} {:?} {:?}\n",
+                begin.line,
+                begin.column,
+                end.line,
+                end.column,
+                token.kind,
+                &source[token.begin..token.end]
+            )
+        })
+        .collect()
+}
+
+// Layout tokens are left out, and every other token is separated by a
+// single space, dropping comments along with the original whitespace.
+pub fn render(tokens: &[Token], source: &str) -> String {
+    tokens
+        .iter()
+        .filter(|token| !token.is_eof() && !matches!(token.kind, TokenK::Layout(_)))
+        .map(|token| &source[token.begin..token.end])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Line comments starting with `-- |` are documentation for the token that
+// follows them; the lexer has already validated the trivia they're in.
+pub fn comments<'a>(token: &Token, source: &'a str) -> Vec<(CommentK, &'a str)> {
+    let trivia = &source[token.comment_begin..token.comment_end];
+    let mut comments = vec![];
+    let mut offset = 0;
+    while offset < trivia.len() {
+        let rest = &trivia[offset..];
+        let length = if rest.starts_with("--") || rest.starts_with("#!") {
+            let length = rest.find('\n').unwrap_or(rest.len());
+            let comment = &rest[..length];
+            if comment.starts_with("-- |") || comment.starts_with("--|") {
+                comments.push((CommentK::Doc, comment));
+            } else if comment.starts_with("--") {
+                comments.push((CommentK::Line, comment));
+            }
+            length
+        } else if rest.starts_with("{-") {
+            let mut cursor = Cursor::new(rest);
+            cursor.take_block_comment();
+            let length = cursor.consumed();
+            comments.push((CommentK::Block, &rest[..length]));
+            length
+        } else {
+            rest.chars().next().map_or(1, char::len_utf8)
+        };
+        offset += length;
+    }
+    comments
+}
+
+pub fn lex_strict(source: &str) -> Result<Vec<Token>, LocatedError> {
+    let tokens = lex(source);
+    let index = LineIndex::new(source);
+    match tokens.iter().find_map(|token| index.locate(token)) {
+        Some(error) => Err(error),
+        None => Ok(tokens),
+    }
+}
+
+pub fn token_at(tokens: &[Token], offset: usize) -> Option<usize> {
+    let index = tokens.partition_point(|token| token.end <= offset);
+    let token = tokens.get(index)?;
+    if token.begin <= offset {
+        Some(index)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        comments, dump, lex, lex_skipping_shebang, lex_strict, lex_with_text, lex_with_trivia,
+        render, token_at,
+        types::{CommentK, LocatedError, TokenK, UnknownK},
+    };
+
+    #[test]
+    fn long_single_line() {
+        let source = format!("f = [{}1]", "1, ".repeat(50_000));
+        let tokens = lex(&source);
+        assert_eq!(tokens.len(), 100_007);
+        assert_eq!(tokens[tokens.len() - 3].end, source.len());
+    }
+
+    #[test]
+    fn locate_after_multibyte() {
+        let source = "λx →\n  ünï 1.";
+        let error = lex(source)
+            .into_iter()
+            .find_map(|token| token.locate(source));
+        assert_eq!(
+            error,
+            Some(LocatedError {
+                line: 2,
+                column: 7,
+                kind: UnknownK::UnfinishedFloat,
+            })
+        );
+    }
+
+    #[test]
+    fn lex_strict_success() {
+        let source = "main = log message";
+        assert_eq!(lex_strict(source), Ok(lex(source)));
+    }
+
+    #[test]
+    fn lex_strict_first_error() {
+        let source = "a = 1.\nb = 0x";
+        assert_eq!(
+            lex_strict(source),
+            Err(LocatedError {
+                line: 1,
+                column: 5,
+                kind: UnknownK::UnfinishedFloat,
+            })
+        );
+    }
+
+    #[test]
+    fn lex_with_text_slices() {
+        let source = "main = log \"hi\"";
+        let slices: Vec<&str> = lex_with_text(source)
+            .filter(|(token, _)| !token.is_eof())
+            .map(|(_, text)| text)
+            .collect();
+        assert_eq!(slices, vec!["main", "=", "log", "\"hi\"", ""]);
+    }
+
+    #[test]
+    fn lex_with_trivia_comments() {
+        let source = "-- doc\nmain = {- inline -} log message";
+        let trivia: Vec<&str> = lex_with_trivia(source)
+            .filter(|(token, _)| matches!(token.kind, TokenK::Identifier(_)))
+            .map(|(_, trivia)| trivia)
+            .collect();
+        assert_eq!(trivia, vec!["-- doc\n", " {- inline -} ", " "]);
+    }
+
+    #[test]
+    fn dump_tokens() {
+        assert_eq!(
+            dump("main\n  \"λ\""),
+            "1:1-1:5 Identifier(Lower) \"main\"\n\
+             2:3-2:6 Literal(String) \"\\\"λ\\\"\"\n\
+             2:6-2:6 Layout(Separator) \"\"\n\
+             2:6-2:6 Unknown(EndOfFile) \"\"\n"
+        );
+    }
+
+    #[test]
+    fn lex_strict_trivia_only() {
+        for source in ["", "   ", "\n\n", "-- comment", "{- comment -}\n"] {
+            let tokens = lex_strict(source).unwrap();
+            assert!(tokens.iter().all(|token| token.begin == source.len()));
+            assert_eq!(tokens.iter().filter(|token| token.is_eof()).count(), 1);
+        }
+    }
+
+    #[test]
+    fn lex_strict_trailing_whitespace() {
+        for source in [
+            "main  \n\n",
+            "main = 1 \n  \n\t\n",
+            "main = do\n  log message\n\n  ",
+        ] {
+            let tokens = lex_strict(source).unwrap();
+            let end_of_file = tokens.iter().filter(|token| token.is_eof());
+            assert_eq!(
+                end_of_file.map(|token| token.begin).collect::<Vec<_>>(),
+                vec![source.len()]
+            );
+        }
+    }
+
+    #[test]
+    fn render_round_trip() {
+        let source = "f x = x + 1";
+        assert_eq!(render(&lex(source), source), source);
+
+        let source = "main  =   log {- note -}  \"a  b\"";
+        assert_eq!(render(&lex(source), source), "main = log \"a  b\"");
+    }
+
+    #[test]
+    fn doc_comments() {
+        let source = "-- |doc\n-- | more\n-- ordinary\n{- a {- b -} -}--| last\nmain = 1";
+        let tokens = lex(source);
+        assert_eq!(
+            comments(&tokens[0], source),
+            vec![
+                (CommentK::Doc, "-- |doc"),
+                (CommentK::Doc, "-- | more"),
+                (CommentK::Line, "-- ordinary"),
+                (CommentK::Block, "{- a {- b -} -}"),
+                (CommentK::Doc, "--| last"),
+            ]
+        );
+        assert_eq!(comments(&tokens[1], source), vec![]);
+
+        for comment in ["{-}-}", "{-{-}-}-}"] {
+            let source = format!("{}\nmain = 1", comment);
+            let tokens = lex(&source);
+            assert_eq!(
+                comments(&tokens[0], &source),
+                vec![(CommentK::Block, comment)]
+            );
+        }
+    }
+
+    #[test]
+    fn shebang_is_skipped() {
+        let source = "#!/usr/bin/env lily\nmain = log message";
+        let tokens = lex_skipping_shebang(source);
+        assert_eq!(tokens[0].comment_begin, 0);
+        assert_eq!(&source[tokens[0].begin..tokens[0].end], "main");
+        assert!(tokens.iter().all(|token| token.locate(source).is_none()));
+    }
+
+    #[test]
+    fn shebang_only_on_first_line() {
+        let source = "main = log message\n#!/usr/bin/env lily";
+        let tokens = lex_skipping_shebang(source);
+        assert_eq!(tokens, lex(source));
+        assert_eq!(lex_skipping_shebang("main"), lex("main"));
+    }
+
+    #[test]
+    fn token_at_boundaries() {
+        let source = "main = do\n  log  message";
+        let tokens = lex(source);
+        let text = |offset| {
+            token_at(&tokens, offset).map(|index| {
+                let token = tokens[index];
+                &source[token.begin..token.end]
+            })
+        };
+        assert_eq!(text(0), Some("main"));
+        assert_eq!(text(3), Some("main"));
+        assert_eq!(text(4), None);
+        assert_eq!(text(5), Some("="));
+        assert_eq!(text(9), None);
+        assert_eq!(text(12), Some("log"));
+        assert_eq!(text(16), None);
+        assert_eq!(text(17), Some("message"));
+        assert_eq!(text(source.len()), None);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::types::{LocatedError, Position, Token, TokenK, UnknownK};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+    tab_width: usize,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+        Self {
+            source,
+            line_starts,
+            tab_width: 1,
+        }
+    }
+
+    // Tabs advance to the next multiple of the tab width; the default of 1
+    // counts them as a single column, matching the layout engine.
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width.max(1);
+        self
+    }
+
+    fn line(&self, offset: usize) -> usize {
+        match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        }
+    }
+
+    // Each unit is a single column, unless it's a tab.
+    fn advance(&self, column: usize, is_tab: bool) -> usize {
+        if is_tab {
+            (column / self.tab_width + 1) * self.tab_width
+        } else {
+            column + 1
+        }
+    }
+
+    fn column(&self, is_tab: impl Iterator<Item = bool>) -> usize {
+        is_tab.fold(0, |column, is_tab| self.advance(column, is_tab)) + 1
+    }
+
+    pub fn line_col(&self, offset: usize) -> Position {
+        let line = self.line(offset);
+        let line_start = self.line_starts[line];
+        // A carriage return before the line feed still counts as a column,
+        // as it is still part of the line it terminates.
+        let column = self.column(self.source[line_start..offset].chars().map(|c| c == '\t'));
+        Position {
+            line: line + 1,
+            column,
+        }
+    }
+
+    // Counts extended grapheme clusters instead of scalar values, which is
+    // what most editors display as a single column.
+    #[cfg(feature = "graphemes")]
+    pub fn grapheme_line_col(&self, offset: usize) -> Position {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        let line = self.line(offset);
+        let line_start = self.line_starts[line];
+        let column = self.column(
+            self.source[line_start..offset]
+                .graphemes(true)
+                .map(|grapheme| grapheme == "\t"),
+        );
+        Position {
+            line: line + 1,
+            column,
+        }
+    }
+
+    pub(crate) fn positions(&self) -> Positions<'_> {
+        Positions {
+            index: self,
+            offset: 0,
+            line: 0,
+            column: 0,
+        }
+    }
+
+    pub fn span(&self, token: &Token) -> (Position, Position) {
+        (self.line_col(token.begin), self.line_col(token.end))
+    }
+
+    pub fn locate(&self, token: &Token) -> Option<LocatedError> {
+        match token.kind {
+            TokenK::Unknown(UnknownK::EndOfFile) => None,
+            TokenK::Unknown(kind) => {
+                let Position { line, column } = self.line_col(token.begin);
+                Some(LocatedError { line, column, kind })
+            }
+            _ => None,
+        }
+    }
+}
+
+// Offsets are expected to be non-decreasing, which lets the walk over the
+// source resume from the last offset rather than the start of the line.
+pub(crate) struct Positions<'a> {
+    index: &'a LineIndex<'a>,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Positions<'a> {
+    pub(crate) fn get(&mut self, offset: usize) -> Position {
+        debug_assert!(self.offset <= offset);
+        for character in self.index.source[self.offset..offset].chars() {
+            if character == '\n' {
+                self.line += 1;
+                self.column = 0;
+            } else {
+                self.column = self.index.advance(self.column, character == '\t');
+            }
+        }
+        self.offset = offset;
+        Position {
+            line: self.line + 1,
+            column: self.column + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LineIndex;
+    use crate::{
+        lex,
+        types::{LocatedError, Position, UnknownK},
+    };
+
+    #[test]
+    fn line_col_with_crlf_and_multibyte() {
+        let source = "λx\r\nünï = 1\r\n";
+        let index = LineIndex::new(source);
+        assert_eq!(index.line_col(0), Position { line: 1, column: 1 });
+        assert_eq!(index.line_col(2), Position { line: 1, column: 2 });
+        assert_eq!(index.line_col(5), Position { line: 2, column: 1 });
+        assert_eq!(index.line_col(11), Position { line: 2, column: 5 });
+        assert_eq!(
+            index.line_col(source.len()),
+            Position { line: 3, column: 1 }
+        );
+    }
+
+    #[test]
+    fn positions_match_full_walk() {
+        let source = "main : Effect Unit\nmain = do\n\tlog \"λ\"\n\n".repeat(128);
+
+        let full_walk = |offset| {
+            let mut line = 1;
+            let mut column = 1;
+            for character in source[..offset].chars() {
+                if character == '\n' {
+                    column = 1;
+                    line += 1;
+                } else {
+                    column += 1;
+                }
+            }
+            Position { line, column }
+        };
+
+        let index = LineIndex::new(&source);
+        let mut positions = index.positions();
+        for (offset, _) in source.char_indices() {
+            assert_eq!(positions.get(offset), full_walk(offset));
+            assert_eq!(index.line_col(offset), full_walk(offset));
+        }
+    }
+
+    #[test]
+    fn line_col_with_tabs() {
+        let source = "\tx\n  \ty\n\t\tz";
+        let default = LineIndex::new(source);
+        assert_eq!(default.line_col(1), Position { line: 1, column: 2 });
+        assert_eq!(default.line_col(6), Position { line: 2, column: 4 });
+
+        let four = LineIndex::new(source).with_tab_width(4);
+        assert_eq!(four.line_col(1), Position { line: 1, column: 5 });
+        assert_eq!(four.line_col(6), Position { line: 2, column: 5 });
+        assert_eq!(four.line_col(10), Position { line: 3, column: 9 });
+
+        let eight = LineIndex::new(source).with_tab_width(8);
+        assert_eq!(eight.line_col(4), Position { line: 2, column: 2 });
+        assert_eq!(eight.line_col(6), Position { line: 2, column: 9 });
+    }
+
+    #[test]
+    fn locate_with_tabs() {
+        let source = "\tmain = 1.";
+        let tokens = lex(source);
+        let error = |index: LineIndex| tokens.iter().find_map(|token| index.locate(token));
+        assert_eq!(
+            error(LineIndex::new(source)),
+            Some(LocatedError {
+                line: 1,
+                column: 9,
+                kind: UnknownK::UnfinishedFloat,
+            })
+        );
+        assert_eq!(
+            error(LineIndex::new(source).with_tab_width(4)),
+            Some(LocatedError {
+                line: 1,
+                column: 12,
+                kind: UnknownK::UnfinishedFloat,
+            })
+        );
+    }
+
+    #[cfg(feature = "graphemes")]
+    #[test]
+    fn grapheme_columns() {
+        let source = "\u{1F1EF}\u{1F1F5} = e\u{301}";
+        let index = LineIndex::new(source);
+        let flag_end = "\u{1F1EF}\u{1F1F5}".len();
+        assert_eq!(index.line_col(flag_end), Position { line: 1, column: 3 });
+        assert_eq!(
+            index.grapheme_line_col(flag_end),
+            Position { line: 1, column: 2 }
+        );
+        assert_eq!(
+            index.grapheme_line_col(source.len()),
+            Position { line: 1, column: 6 }
+        );
+    }
+
+    #[test]
+    fn token_spans() {
+        let source = "main =\r\n  ünï";
+        let index = LineIndex::new(source);
+        let spans: Vec<_> = lex(source)
+            .iter()
+            .filter(|token| token.end > token.begin)
+            .map(|token| index.span(token))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (
+                    Position { line: 1, column: 1 },
+                    Position { line: 1, column: 5 }
+                ),
+                (
+                    Position { line: 1, column: 6 },
+                    Position { line: 1, column: 7 }
+                ),
+                (
+                    Position { line: 2, column: 3 },
+                    Position { line: 2, column: 6 }
+                ),
+            ]
+        );
+    }
+}
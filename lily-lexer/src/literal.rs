@@ -0,0 +1,178 @@
+use std::{num::IntErrorKind, str::Chars};
+
+fn escape(chars: &mut Chars) -> Option<char> {
+    match chars.next()? {
+        '0' => Some('\0'),
+        'n' => Some('\n'),
+        'r' => Some('\r'),
+        't' => Some('\t'),
+        '\\' => Some('\\'),
+        '\'' => Some('\''),
+        '"' => Some('"'),
+        'u' => {
+            if chars.next()? != '{' {
+                return None;
+            }
+            let mut value = 0;
+            let mut length = 0;
+            loop {
+                match chars.next()? {
+                    '}' if length > 0 => break,
+                    digit if length < 6 => {
+                        value = value * 16 + digit.to_digit(16)?;
+                        length += 1;
+                    }
+                    _ => return None,
+                }
+            }
+            char::from_u32(value)
+        }
+        _ => None,
+    }
+}
+
+pub fn character(source: &str) -> Option<char> {
+    let inner = source.strip_prefix('\'')?.strip_suffix('\'')?;
+    let mut chars = inner.chars();
+    let character = match chars.next()? {
+        '\\' => escape(&mut chars)?,
+        character => character,
+    };
+    if chars.next().is_some() {
+        return None;
+    }
+    Some(character)
+}
+
+pub fn string(source: &str) -> Option<String> {
+    let inner = source.strip_prefix('"')?.strip_suffix('"')?;
+    let mut chars = inner.chars();
+    let mut string = String::with_capacity(inner.len());
+    while let Some(character) = chars.next() {
+        match character {
+            '\\' => string.push(escape(&mut chars)?),
+            character => string.push(character),
+        }
+    }
+    Some(string)
+}
+
+// The first line break after the opening quotes is dropped, and so is the
+// indentation shared by all non-blank lines, counted in characters.
+pub fn multiline_string(source: &str) -> Option<String> {
+    let inner = source.strip_prefix("\"\"\"")?.strip_suffix("\"\"\"")?;
+    let inner = inner
+        .strip_prefix("\r\n")
+        .or_else(|| inner.strip_prefix('\n'))
+        .unwrap_or(inner);
+    let indentation = inner
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.chars().count() - line.trim_start().chars().count())
+        .min()
+        .unwrap_or(0);
+    let lines: Vec<&str> = inner
+        .split('\n')
+        .map(|line| match line.char_indices().nth(indentation) {
+            Some((offset, _)) => &line[offset..],
+            None => line.trim_start(),
+        })
+        .collect();
+    Some(lines.join("\n"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegerError {
+    Malformed,
+    Overflow,
+}
+
+pub fn integer(source: &str) -> Result<u64, IntegerError> {
+    let (digits, radix) = match source.get(..2) {
+        Some("0x") => (&source[2..], 16),
+        Some("0o") => (&source[2..], 8),
+        Some("0b") => (&source[2..], 2),
+        _ => (source, 10),
+    };
+    u64::from_str_radix(&digits.replace('_', ""), radix).map_err(|error| match error.kind() {
+        IntErrorKind::PosOverflow => IntegerError::Overflow,
+        _ => IntegerError::Malformed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{character, integer, multiline_string, string, IntegerError};
+
+    #[test]
+    fn plain_character() {
+        assert_eq!(character("'a'"), Some('a'));
+        assert_eq!(character("'λ'"), Some('λ'));
+    }
+
+    #[test]
+    fn escaped_character() {
+        assert_eq!(character(r"'\n'"), Some('\n'));
+        assert_eq!(character(r"'\t'"), Some('\t'));
+        assert_eq!(character(r"'\\'"), Some('\\'));
+        assert_eq!(character(r"'\''"), Some('\''));
+    }
+
+    #[test]
+    fn unicode_escapes() {
+        assert_eq!(character(r"'\u{41}'"), Some('A'));
+        assert_eq!(string(r#""\u{1F600}!""#).as_deref(), Some("\u{1F600}!"));
+        assert_eq!(string(r#""\u{}""#), None);
+        assert_eq!(string(r#""\u{110000}""#), None);
+        assert_eq!(string(r#""\u{D800}""#), None);
+        assert_eq!(string(r#""\u{0000041}""#), None);
+        assert_eq!(string(r#""\u{41""#), None);
+    }
+
+    #[test]
+    fn malformed_character() {
+        assert_eq!(character("''"), None);
+        assert_eq!(character("'a"), None);
+        assert_eq!(character("'ab'"), None);
+        assert_eq!(character(r"'\q'"), None);
+    }
+
+    #[test]
+    fn integer_literals() {
+        assert_eq!(integer("42"), Ok(42));
+        assert_eq!(integer("1_000_000"), Ok(1_000_000));
+        assert_eq!(integer("0x1F"), Ok(0x1F));
+        assert_eq!(integer("0o17"), Ok(0o17));
+        assert_eq!(integer("0b1010"), Ok(0b1010));
+        assert_eq!(integer("18446744073709551615"), Ok(u64::MAX));
+        assert_eq!(integer("18446744073709551616"), Err(IntegerError::Overflow));
+        assert_eq!(
+            integer("0x1_0000_0000_0000_0000"),
+            Err(IntegerError::Overflow)
+        );
+        assert_eq!(integer("0x"), Err(IntegerError::Malformed));
+        assert_eq!(integer("0b12"), Err(IntegerError::Malformed));
+    }
+
+    #[test]
+    fn multiline_string_indentation() {
+        let source = "\"\"\"\n    one\n      two\n    three\n  \"\"\"";
+        assert_eq!(
+            multiline_string(source).as_deref(),
+            Some("one\n  two\nthree\n")
+        );
+        let source = "\"\"\"\r\n  a\r\n  b\r\n\"\"\"";
+        assert_eq!(multiline_string(source).as_deref(), Some("a\r\nb\r\n"));
+        let source = "\"\"\"\n\t  a\n\t    b\n\"\"\"";
+        assert_eq!(multiline_string(source).as_deref(), Some("a\n  b\n"));
+        let source = "\"\"\"\n\u{3000}  a\n    b\n\"\"\"";
+        assert_eq!(multiline_string(source).as_deref(), Some("a\n b\n"));
+    }
+
+    #[test]
+    fn string_literals() {
+        assert_eq!(string(r#""a\"b""#).as_deref(), Some("a\"b"));
+        assert_eq!(string(r#""\n\t\\""#).as_deref(), Some("\n\t\\"));
+        assert_eq!(string(r#""\q""#), None);
+    }
+}
@@ -1,16 +1,27 @@
+use std::fmt;
+
+use crate::line_index::LineIndex;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum CommentK {
     Block,
+    Doc,
     Line,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum IdentifierK {
     Ado,
     As,
     Case,
     Do,
     Else,
+    False,
+    Forall,
     If,
     In,
     Infixl,
@@ -18,12 +29,44 @@ pub enum IdentifierK {
     Let,
     Lower,
     Of,
+    QualifiedLower,
+    QualifiedUpper,
     Then,
+    True,
     Type,
     Upper,
 }
 
+impl IdentifierK {
+    pub fn as_keyword(&self) -> Option<&'static str> {
+        match self {
+            IdentifierK::Ado => Some("ado"),
+            IdentifierK::As => Some("as"),
+            IdentifierK::Case => Some("case"),
+            IdentifierK::Do => Some("do"),
+            IdentifierK::Else => Some("else"),
+            IdentifierK::False => Some("false"),
+            IdentifierK::Forall => Some("forall"),
+            IdentifierK::If => Some("if"),
+            IdentifierK::In => Some("in"),
+            IdentifierK::Infixl => Some("infixl"),
+            IdentifierK::Infixr => Some("infixr"),
+            IdentifierK::Let => Some("let"),
+            IdentifierK::Of => Some("of"),
+            IdentifierK::Then => Some("then"),
+            IdentifierK::True => Some("true"),
+            IdentifierK::Type => Some("type"),
+            IdentifierK::Lower
+            | IdentifierK::QualifiedLower
+            | IdentifierK::QualifiedUpper
+            | IdentifierK::Upper => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum DelimiterK {
     Round,
     Square,
@@ -31,7 +74,10 @@ pub enum DelimiterK {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum OperatorK {
+    ArrowConstraint,
     ArrowLeft,
     ArrowRight,
     Backslash,
@@ -41,6 +87,7 @@ pub enum OperatorK {
     Equal,
     GreaterThan,
     LessThan,
+    Minus,
     Period,
     Pipe,
     Question,
@@ -49,20 +96,59 @@ pub enum OperatorK {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum DigitK {
     Float,
     Int,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum LiteralK {
+    Character,
+    MultilineString,
+    String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum UnknownK {
+    InvalidEscape,
+    InvalidUnicodeEscape,
+    MalformedFloat,
+    MalformedInteger,
+    UnfinishedCharacter,
     UnfinishedComment,
     UnfinishedFloat,
+    UnfinishedString,
     UnknownToken,
     EndOfFile,
 }
 
+impl fmt::Display for UnknownK {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            UnknownK::InvalidEscape => "invalid escape sequence",
+            UnknownK::InvalidUnicodeEscape => "invalid unicode escape sequence",
+            UnknownK::MalformedFloat => "malformed float literal",
+            UnknownK::MalformedInteger => "malformed integer literal",
+            UnknownK::UnfinishedCharacter => "unterminated character literal",
+            UnknownK::UnfinishedComment => "unterminated block comment",
+            UnknownK::UnfinishedFloat => "unterminated float literal",
+            UnknownK::UnfinishedString => "unterminated string literal",
+            UnknownK::UnknownToken => "unknown token",
+            UnknownK::EndOfFile => "end of file",
+        };
+        write!(f, "{}", description)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum LayoutK {
     Begin,
     End,
@@ -70,17 +156,68 @@ pub enum LayoutK {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum TokenK {
     CloseDelimiter(DelimiterK),
     Digit(DigitK),
     Identifier(IdentifierK),
     Layout(LayoutK),
+    Literal(LiteralK),
     OpenDelimiter(DelimiterK),
     Operator(OperatorK),
     Unknown(UnknownK),
 }
 
+impl fmt::Display for TokenK {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            TokenK::CloseDelimiter(DelimiterK::Round) => "`)`",
+            TokenK::CloseDelimiter(DelimiterK::Square) => "`]`",
+            TokenK::CloseDelimiter(DelimiterK::Brace) => "`}`",
+            TokenK::Digit(DigitK::Float) => "float literal",
+            TokenK::Digit(DigitK::Int) => "integer literal",
+            TokenK::Identifier(IdentifierK::Lower) => "identifier",
+            TokenK::Identifier(IdentifierK::Upper) => "upper-case identifier",
+            TokenK::Identifier(IdentifierK::QualifiedLower) => "qualified identifier",
+            TokenK::Identifier(IdentifierK::QualifiedUpper) => "qualified upper-case identifier",
+            TokenK::Identifier(keyword) => {
+                let keyword = keyword.as_keyword().unwrap_or_default();
+                return write!(f, "keyword `{}`", keyword);
+            }
+            TokenK::Layout(LayoutK::Begin) => "start of layout block",
+            TokenK::Layout(LayoutK::End) => "end of layout block",
+            TokenK::Layout(LayoutK::Separator) => "layout separator",
+            TokenK::Literal(LiteralK::Character) => "character literal",
+            TokenK::Literal(LiteralK::MultilineString) => "multi-line string literal",
+            TokenK::Literal(LiteralK::String) => "string literal",
+            TokenK::OpenDelimiter(DelimiterK::Round) => "`(`",
+            TokenK::OpenDelimiter(DelimiterK::Square) => "`[`",
+            TokenK::OpenDelimiter(DelimiterK::Brace) => "`{`",
+            TokenK::Operator(OperatorK::ArrowLeft) => "`<-`",
+            TokenK::Operator(OperatorK::ArrowRight) => "`->`",
+            TokenK::Operator(OperatorK::ArrowConstraint) => "`=>`",
+            TokenK::Operator(OperatorK::Backslash) => "`\\`",
+            TokenK::Operator(OperatorK::Bang) => "`!`",
+            TokenK::Operator(OperatorK::Colon) => "`:`",
+            TokenK::Operator(OperatorK::Comma) => "`,`",
+            TokenK::Operator(OperatorK::Equal) => "`=`",
+            TokenK::Operator(OperatorK::GreaterThan) => "`>`",
+            TokenK::Operator(OperatorK::LessThan) => "`<`",
+            TokenK::Operator(OperatorK::Minus) => "`-`",
+            TokenK::Operator(OperatorK::Period) => "`.`",
+            TokenK::Operator(OperatorK::Pipe) => "`|`",
+            TokenK::Operator(OperatorK::Question) => "`?`",
+            TokenK::Operator(OperatorK::Source) => "operator",
+            TokenK::Operator(OperatorK::Underscore) => "`_`",
+            TokenK::Unknown(unknown) => return write!(f, "{}", unknown),
+        };
+        write!(f, "{}", description)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Token {
     pub comment_begin: usize,
     pub comment_end: usize,
@@ -99,6 +236,38 @@ impl Token {
         self.depth == 0 && matches!(self.kind, TokenK::Layout(LayoutK::Separator))
     }
 
+    pub fn is_name(&self) -> bool {
+        matches!(
+            self.kind,
+            TokenK::Identifier(
+                IdentifierK::Lower
+                    | IdentifierK::Upper
+                    | IdentifierK::QualifiedLower
+                    | IdentifierK::QualifiedUpper
+            )
+        )
+    }
+
+    pub fn is_operator(&self) -> bool {
+        matches!(self.kind, TokenK::Operator(_))
+    }
+
+    pub fn is_literal(&self) -> bool {
+        matches!(self.kind, TokenK::Digit(_) | TokenK::Literal(_))
+    }
+
+    pub fn is_layout(&self) -> bool {
+        matches!(self.kind, TokenK::Layout(_))
+    }
+
+    pub fn is_error(&self) -> bool {
+        !self.is_eof() && matches!(self.kind, TokenK::Unknown(_))
+    }
+
+    pub fn is_keyword(&self) -> bool {
+        matches!(self.kind, TokenK::Identifier(kind) if kind.as_keyword().is_some())
+    }
+
     pub fn is_infix_identifier(&self) -> bool {
         matches!(
             self.kind,
@@ -121,7 +290,7 @@ impl Token {
             TokenK::Identifier(IdentifierK::Then | IdentifierK::Else | IdentifierK::Of)
                 | TokenK::Operator(OperatorK::Comma | OperatorK::ArrowRight)
                 | TokenK::Layout(LayoutK::Separator)
-                | TokenK::CloseDelimiter(DelimiterK::Round)
+                | TokenK::CloseDelimiter(_)
         )
     }
 
@@ -130,14 +299,16 @@ impl Token {
             self.kind,
             TokenK::Identifier(
                 IdentifierK::If | IdentifierK::Do | IdentifierK::Case | IdentifierK::Let
-            )
+            ) | TokenK::Operator(OperatorK::Backslash)
         )
     }
 
     pub fn is_ty_boundary(&self) -> bool {
         matches!(
             self.kind,
-            TokenK::Layout(LayoutK::Separator) | TokenK::CloseDelimiter(DelimiterK::Round)
+            TokenK::Layout(LayoutK::Separator)
+                | TokenK::CloseDelimiter(DelimiterK::Round)
+                | TokenK::Operator(OperatorK::ArrowConstraint)
         )
     }
 
@@ -145,10 +316,130 @@ impl Token {
         self.depth = depth;
         self
     }
+
+    // Only error tokens pay for indexing the source; locating many tokens
+    // is better served by a single `LineIndex`.
+    pub fn locate(&self, source: &str) -> Option<LocatedError> {
+        if self.is_error() {
+            LineIndex::new(source).locate(self)
+        } else {
+            None
+        }
+    }
+
+    pub fn display(self, source: &str) -> TokenDisplay<'_> {
+        TokenDisplay {
+            token: self,
+            source,
+        }
+    }
+}
+
+pub struct TokenDisplay<'a> {
+    token: Token,
+    source: &'a str,
+}
+
+impl<'a> fmt::Display for TokenDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.token.kind {
+            TokenK::Layout(LayoutK::Begin) => write!(f, "{{"),
+            TokenK::Layout(LayoutK::End) => write!(f, "}}"),
+            TokenK::Layout(LayoutK::Separator) => write!(f, ";"),
+            _ => write!(f, "{}", &self.source[self.token.begin..self.token.end]),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     pub line: usize,
     pub column: usize,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LocatedError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: UnknownK,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DelimiterK, DigitK, IdentifierK, LayoutK, OperatorK, TokenK, UnknownK};
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let tokens = crate::lex("main = \"unfinished");
+        let json = serde_json::to_string(&tokens).unwrap();
+        let decoded: Vec<super::Token> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, tokens);
+    }
+
+    #[test]
+    fn categorize_tokens() {
+        let source = "f = Data.List.map 1 \"a\" let +\n'";
+        let tokens = crate::lex(source);
+        let categories: Vec<_> = tokens
+            .iter()
+            .map(|token| {
+                [
+                    token.is_name(),
+                    token.is_keyword(),
+                    token.is_operator(),
+                    token.is_literal(),
+                    token.is_layout(),
+                    token.is_error(),
+                ]
+            })
+            .collect();
+        let kinds: Vec<_> = tokens.iter().map(|token| token.kind).collect();
+        assert_eq!(kinds.len(), categories.len());
+        for (kind, category) in kinds.iter().zip(&categories) {
+            let expected = match kind {
+                TokenK::Identifier(IdentifierK::Let) => [false, true, false, false, false, false],
+                TokenK::Identifier(_) => [true, false, false, false, false, false],
+                TokenK::Operator(_) => [false, false, true, false, false, false],
+                TokenK::Digit(_) | TokenK::Literal(_) => [false, false, false, true, false, false],
+                TokenK::Layout(_) => [false, false, false, false, true, false],
+                TokenK::Unknown(UnknownK::EndOfFile) => [false; 6],
+                _ => [false, false, false, false, false, true],
+            };
+            assert_eq!(category, &expected, "{:?}", kind);
+        }
+        assert!(kinds.contains(&TokenK::Unknown(UnknownK::UnfinishedCharacter)));
+    }
+
+    #[test]
+    fn display_token_kinds() {
+        let descriptions: Vec<String> = [
+            TokenK::OpenDelimiter(DelimiterK::Round),
+            TokenK::Digit(DigitK::Int),
+            TokenK::Identifier(IdentifierK::Lower),
+            TokenK::Identifier(IdentifierK::Let),
+            TokenK::Layout(LayoutK::Separator),
+            TokenK::Operator(OperatorK::ArrowRight),
+            TokenK::Operator(OperatorK::Source),
+            TokenK::Unknown(UnknownK::UnfinishedString),
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "`(`",
+                "integer literal",
+                "identifier",
+                "keyword `let`",
+                "layout separator",
+                "`->`",
+                "operator",
+                "unterminated string literal",
+            ]
+        );
+    }
+}
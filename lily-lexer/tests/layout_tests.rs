@@ -12,6 +12,7 @@ fn lex_print(source: &str) -> String {
                 LayoutK::Begin => buffer.push_str(format!("{{{}", token.depth).as_str()),
                 LayoutK::End => buffer.push_str(format!("}}{}", token.depth).as_str()),
                 LayoutK::Separator => buffer.push_str(format!(";{}", token.depth).as_str()),
+                layout => panic!("Unexpected layout token {:?}", layout),
             }
         } else {
             buffer.push_str(&source[token.comment_begin..token.comment_end]);
@@ -168,3 +169,36 @@ adoLet = do
 
     insta::assert_snapshot!(lex_print(source));
 }
+
+#[test]
+fn display_0() {
+    let source = r"main = do
+  log message";
+
+    let rendered: Vec<String> = lex(source)
+        .into_iter()
+        .filter(|token| !token.is_eof())
+        .map(|token| token.display(source).to_string())
+        .collect();
+    assert_eq!(rendered.join(" "), "main = do { log message ; } ;");
+}
+
+#[test]
+fn layout_12() {
+    let source = r"nested : Effect Unit
+nested = do
+  let
+    x = do
+      let y = 1
+      pure y
+  log x";
+
+    insta::assert_snapshot!(lex_print(source));
+}
+
+#[test]
+fn layout_13() {
+    let source = "λ = do\n  x\n  y\nz = 1";
+
+    insta::assert_snapshot!(lex_print(source));
+}
@@ -16,7 +16,7 @@ impl<'a> Cursor<'a> {
             (begin, SmolStr::new(&self.source[begin..end]))
         };
 
-        if let TokenK::Operator(OperatorK::Colon) = self.peek()?.kind {
+        if let TokenK::Operator(OperatorK::Colon) = self.peek_kind()? {
             self.take()?;
             let ty @ Ty {
                 end: declaration_end,
@@ -31,7 +31,7 @@ impl<'a> Cursor<'a> {
         }
 
         let lesser_patterns = self.lesser_patterns()?;
-        if let TokenK::Operator(OperatorK::Equal) = self.peek()?.kind {
+        if let TokenK::Operator(OperatorK::Equal) = self.peek_kind()? {
             self.take()?;
             let (declaration_end, expression) = {
                 let expression = self.expression()?;
@@ -45,31 +45,40 @@ impl<'a> Cursor<'a> {
             });
         }
 
-        bail!(ParseError::UnexpectedToken(self.peek()?.kind));
+        bail!(ParseError::UnexpectedToken(
+            self.peek()?,
+            vec![TokenK::Operator(OperatorK::Equal)]
+        ));
     }
 
     pub fn declaration_let(&mut self) -> anyhow::Result<Declaration> {
-        if let TokenK::Identifier(IdentifierK::Lower) = self.peek()?.kind {
+        if let TokenK::Identifier(IdentifierK::Lower) = self.peek_kind()? {
             return self.declaration_lower();
         }
-        bail!(ParseError::UnexpectedToken(self.peek()?.kind))
+        bail!(ParseError::UnexpectedToken(
+            self.peek()?,
+            vec![TokenK::Identifier(IdentifierK::Lower)]
+        ))
     }
 
     pub fn declaration_let_block(&mut self) -> anyhow::Result<Vec<Declaration>> {
-        let mut declarations = vec![self.declaration_let()?];
+        let mut declarations = vec![];
         loop {
-            if let TokenK::Layout(LayoutK::End) = self.peek()?.kind {
+            declarations.extend(self.recover(Self::declaration_let)?);
+            if let TokenK::Layout(LayoutK::End) = self.peek_kind()? {
                 break;
             }
-            declarations.push(self.declaration_let()?);
         }
-        Ok(declarations)
+        self.non_empty(declarations)
     }
 
     pub fn declaration(&mut self) -> anyhow::Result<Declaration> {
-        if let TokenK::Identifier(IdentifierK::Lower) = self.peek()?.kind {
+        if let TokenK::Identifier(IdentifierK::Lower) = self.peek_kind()? {
             return self.declaration_lower();
         }
-        bail!(ParseError::UnexpectedToken(self.peek()?.kind));
+        bail!(ParseError::UnexpectedToken(
+            self.peek()?,
+            vec![TokenK::Identifier(IdentifierK::Lower)]
+        ));
     }
 }
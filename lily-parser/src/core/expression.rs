@@ -5,16 +5,19 @@ use smol_str::SmolStr;
 use crate::{
     cursor::{expect_token, Cursor},
     errors::ParseError,
-    types::{CaseArm, DoStatement, DoStatementK, Expression, ExpressionK, LesserPattern},
+    types::{
+        CaseArm, DoStatement, DoStatementK, Expression, ExpressionK, HasSpan, LesserPattern, Span,
+    },
 };
 
 impl<'a> Cursor<'a> {
     fn expression_atom(&mut self) -> anyhow::Result<Expression> {
-        let Token {
+        let token @ Token {
             begin, end, kind, ..
         } = self.take()?;
 
         if let TokenK::Digit(DigitK::Int) = kind {
+            self.check_integer(token)?;
             return Ok(Expression {
                 begin,
                 end,
@@ -30,7 +33,32 @@ impl<'a> Cursor<'a> {
             });
         }
 
-        if let TokenK::Identifier(IdentifierK::Lower) = kind {
+        // A minus attached to a digit is only a negative literal where an
+        // operand is expected; after an operand, it's still subtraction.
+        if let TokenK::Operator(OperatorK::Minus) = kind {
+            let digits @ Token { end, kind, .. } = expect_token!(
+                self,
+                TokenK::Digit(DigitK::Int),
+                TokenK::Digit(DigitK::Float)
+            );
+            self.check_integer(digits)?;
+            let literal = SmolStr::new(&self.source[begin..end]);
+            let kind = match kind {
+                TokenK::Digit(DigitK::Int) => ExpressionK::Integer(literal),
+                _ => ExpressionK::Float(literal),
+            };
+            return Ok(Expression { begin, end, kind });
+        }
+
+        if let TokenK::Identifier(IdentifierK::True | IdentifierK::False) = kind {
+            return Ok(Expression {
+                begin,
+                end,
+                kind: ExpressionK::Boolean(kind == TokenK::Identifier(IdentifierK::True)),
+            });
+        }
+
+        if let TokenK::Identifier(IdentifierK::Lower | IdentifierK::QualifiedLower) = kind {
             return Ok(Expression {
                 begin,
                 end,
@@ -38,7 +66,7 @@ impl<'a> Cursor<'a> {
             });
         }
 
-        if let TokenK::Identifier(IdentifierK::Upper) = kind {
+        if let TokenK::Identifier(IdentifierK::Upper | IdentifierK::QualifiedUpper) = kind {
             return Ok(Expression {
                 begin,
                 end,
@@ -47,7 +75,35 @@ impl<'a> Cursor<'a> {
         }
 
         if let TokenK::OpenDelimiter(DelimiterK::Round) = kind {
+            // `(- 1)` is a section like any other, while `(-1)` is a negative
+            // literal, as the lexer only attaches a minus directly to a digit.
+            if let TokenK::Operator(OperatorK::Source) = self.peek_kind()? {
+                let Token { begin, end, .. } = self.take()?;
+                let operator = SmolStr::new(&self.source[begin..end]);
+                let expression = self.expression_core(0)?;
+                let Token { end, .. } =
+                    expect_token!(self, TokenK::CloseDelimiter(DelimiterK::Round));
+                return Ok(Expression {
+                    begin: token.begin,
+                    end,
+                    kind: ExpressionK::RightSection(operator, Box::new(expression)),
+                });
+            }
+
             let expression = self.expression_core(0)?;
+
+            if let TokenK::Operator(OperatorK::Minus | OperatorK::Source) = self.peek_kind()? {
+                let Token { begin, end, .. } = self.take()?;
+                let operator = SmolStr::new(&self.source[begin..end]);
+                let Token { end, .. } =
+                    expect_token!(self, TokenK::CloseDelimiter(DelimiterK::Round));
+                return Ok(Expression {
+                    begin: token.begin,
+                    end,
+                    kind: ExpressionK::LeftSection(Box::new(expression), operator),
+                });
+            }
+
             let Token { end, .. } = expect_token!(self, TokenK::CloseDelimiter(DelimiterK::Round));
             return Ok(Expression {
                 begin,
@@ -56,7 +112,55 @@ impl<'a> Cursor<'a> {
             });
         }
 
-        bail!(ParseError::UnexpectedToken(kind));
+        if let TokenK::OpenDelimiter(DelimiterK::Square) = kind {
+            let elements = self.expression_elements(DelimiterK::Square, Self::expression)?;
+            let Token { end, .. } = expect_token!(self, TokenK::CloseDelimiter(DelimiterK::Square));
+            return Ok(Expression {
+                begin,
+                end,
+                kind: ExpressionK::Array(elements),
+            });
+        }
+
+        if let TokenK::OpenDelimiter(DelimiterK::Brace) = kind {
+            let fields = self.expression_elements(DelimiterK::Brace, Self::expression_field)?;
+            let Token { end, .. } = expect_token!(self, TokenK::CloseDelimiter(DelimiterK::Brace));
+            return Ok(Expression {
+                begin,
+                end,
+                kind: ExpressionK::Record(fields),
+            });
+        }
+
+        bail!(ParseError::UnexpectedToken(token, vec![]));
+    }
+
+    // Elements are separated by commas; trailing commas are not allowed,
+    // consistent with the expressions in a case head.
+    fn expression_elements<T>(
+        &mut self,
+        close: DelimiterK,
+        element: impl Fn(&mut Self) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Vec<T>> {
+        let mut elements = vec![];
+        if self.peek_kind()? == TokenK::CloseDelimiter(close) {
+            return Ok(elements);
+        }
+        loop {
+            elements.push(element(self)?);
+            if let TokenK::Operator(OperatorK::Comma) = self.peek_kind()? {
+                self.take()?;
+                continue;
+            }
+            break Ok(elements);
+        }
+    }
+
+    fn expression_field(&mut self) -> anyhow::Result<(SmolStr, Expression)> {
+        let Token { begin, end, .. } = expect_token!(self, TokenK::Identifier(IdentifierK::Lower));
+        expect_token!(self, TokenK::Operator(OperatorK::Equal));
+        let expression = self.expression()?;
+        Ok((SmolStr::new(&self.source[begin..end]), expression))
     }
 
     fn expression_if(&mut self) -> anyhow::Result<Expression> {
@@ -87,7 +191,7 @@ impl<'a> Cursor<'a> {
             ..
         } = expect_token!(self, TokenK::Identifier(IdentifierK::Do));
 
-        if let TokenK::Layout(LayoutK::Separator) = self.peek()?.kind {
+        if let TokenK::Layout(LayoutK::Separator) = self.peek_kind()? {
             return Ok(Expression {
                 begin: do_begin,
                 end: do_end,
@@ -115,7 +219,7 @@ impl<'a> Cursor<'a> {
     }
 
     fn expression_do_statement(&mut self) -> anyhow::Result<DoStatement> {
-        if let TokenK::Identifier(IdentifierK::Let) = self.peek()?.kind {
+        if let TokenK::Identifier(IdentifierK::Let) = self.peek_kind()? {
             let Token {
                 begin: let_begin, ..
             } = expect_token!(self, TokenK::Identifier(IdentifierK::Let));
@@ -148,7 +252,7 @@ impl<'a> Cursor<'a> {
             return do_statement;
         }
 
-        bail!(ParseError::UnexpectedToken(self.peek()?.kind));
+        bail!(ParseError::UnexpectedToken(self.peek()?, vec![]));
     }
 
     fn expression_do_statement_bind(&mut self) -> anyhow::Result<DoStatement> {
@@ -176,12 +280,12 @@ impl<'a> Cursor<'a> {
     fn expression_do_statements(&mut self) -> anyhow::Result<Vec<DoStatement>> {
         let mut statements: Vec<DoStatement> = vec![];
         loop {
-            if let TokenK::Layout(LayoutK::End) = self.peek()?.kind {
+            if let TokenK::Layout(LayoutK::End) = self.peek_kind()? {
                 break;
             }
-            statements.push(self.expression_do_statement()?);
+            statements.extend(self.recover(Self::expression_do_statement)?);
         }
-        Ok(statements)
+        self.non_empty(statements)
     }
 
     fn expression_case(&mut self) -> anyhow::Result<Expression> {
@@ -215,12 +319,12 @@ impl<'a> Cursor<'a> {
     fn expression_case_expressions(&mut self) -> anyhow::Result<Vec<Expression>> {
         let mut expressions = vec![self.expression()?];
         loop {
-            if let TokenK::Operator(OperatorK::Comma) = self.peek()?.kind {
-                self.take()?;
-                continue;
-            }
-            if let TokenK::Identifier(IdentifierK::Of) = self.peek()?.kind {
-                self.take()?;
+            let Token { kind, .. } = expect_token!(
+                self,
+                TokenK::Operator(OperatorK::Comma),
+                TokenK::Identifier(IdentifierK::Of),
+            );
+            if let TokenK::Identifier(IdentifierK::Of) = kind {
                 break;
             }
             expressions.push(self.expression()?);
@@ -230,7 +334,7 @@ impl<'a> Cursor<'a> {
 
     fn expression_case_arm(&mut self) -> anyhow::Result<CaseArm> {
         let patterns = self.greater_patterns()?;
-        let condition = if let TokenK::Identifier(IdentifierK::If) = self.peek()?.kind {
+        let condition = if let TokenK::Identifier(IdentifierK::If) = self.peek_kind()? {
             self.take()?;
             Some(self.expression()?)
         } else {
@@ -247,57 +351,75 @@ impl<'a> Cursor<'a> {
     }
 
     fn expression_case_arms(&mut self) -> anyhow::Result<Vec<CaseArm>> {
-        let mut arms = vec![self.expression_case_arm()?];
+        let mut arms = vec![];
         loop {
-            if let TokenK::Layout(LayoutK::End) = self.peek()?.kind {
+            arms.extend(self.recover(Self::expression_case_arm)?);
+            if let TokenK::Layout(LayoutK::End) = self.peek_kind()? {
                 break;
             }
-            arms.push(self.expression_case_arm()?);
         }
-        Ok(arms)
+        self.non_empty(arms)
     }
 
     fn expression_let(&mut self) -> anyhow::Result<Expression> {
-        let Token {
-            begin: let_begin, ..
-        } = expect_token!(self, TokenK::Identifier(IdentifierK::Let));
+        let let_token = expect_token!(self, TokenK::Identifier(IdentifierK::Let));
 
         expect_token!(self, TokenK::Layout(LayoutK::Begin));
 
         let declarations = self.declaration_let_block()?;
-        let let_end = declarations
-            .last()
-            .context(ParseError::InternalError(
-                "Cannot determine last declaration".into(),
-            ))?
-            .end;
 
         expect_token!(self, TokenK::Layout(LayoutK::End));
 
         expect_token!(self, TokenK::Identifier(IdentifierK::In));
 
         let expression = self.expression()?;
+        let Span { begin, end } = Span::from(let_token).merge(expression.span());
 
         Ok(Expression {
-            begin: let_begin,
-            end: let_end,
+            begin,
+            end,
             kind: ExpressionK::Let(declarations, Box::new(expression)),
         })
     }
 
+    fn expression_lambda(&mut self) -> anyhow::Result<Expression> {
+        let backslash = expect_token!(self, TokenK::Operator(OperatorK::Backslash));
+
+        let mut arguments = vec![self.lesser_pattern()?];
+        loop {
+            if let TokenK::Operator(OperatorK::ArrowRight) = self.peek_kind()? {
+                self.take()?;
+                break;
+            }
+            arguments.push(self.lesser_pattern()?);
+        }
+
+        let expression = self.expression()?;
+        let Span { begin, end } = Span::from(backslash).merge(expression.span());
+
+        Ok(Expression {
+            begin,
+            end,
+            kind: ExpressionK::Lambda(arguments, Box::new(expression)),
+        })
+    }
+
     fn expression_core(&mut self, minimum_power: u8) -> anyhow::Result<Expression> {
-        if let TokenK::Identifier(IdentifierK::If) = self.peek()?.kind {
+        if let TokenK::Identifier(IdentifierK::If) = self.peek_kind()? {
             return self.expression_if();
         }
-        if let TokenK::Identifier(IdentifierK::Do) = self.peek()?.kind {
+        if let TokenK::Identifier(IdentifierK::Do) = self.peek_kind()? {
             return self.expression_do();
         }
-        if let TokenK::Identifier(IdentifierK::Case) = self.peek()?.kind {
+        if let TokenK::Identifier(IdentifierK::Case) = self.peek_kind()? {
             return self.expression_case();
         }
-        if let TokenK::Identifier(IdentifierK::Let) = self.peek()?.kind {
+        if let TokenK::Identifier(IdentifierK::Let) = self.peek_kind()? {
             return self.expression_let();
         }
+        if let TokenK::Operator(OperatorK::Backslash) = self.peek_kind()? {
+            return self.expression_lambda();
+        }
 
         let mut accumulator = self.expression_atom()?;
 
@@ -307,19 +429,21 @@ impl<'a> Cursor<'a> {
             }
 
             if self.peek()?.is_block_argument() {
-                let argument = match self.peek()?.kind {
+                let argument = match self.peek_kind()? {
                     TokenK::Identifier(IdentifierK::If) => self.expression_if()?,
                     TokenK::Identifier(IdentifierK::Do) => self.expression_do()?,
                     TokenK::Identifier(IdentifierK::Case) => self.expression_case()?,
                     TokenK::Identifier(IdentifierK::Let) => self.expression_let()?,
+                    TokenK::Operator(OperatorK::Backslash) => self.expression_lambda()?,
                     kind => bail!(ParseError::InternalError(format!(
                         "Unhandled block argument '{:?}'",
                         kind
                     ))),
                 };
+                let Span { begin, end } = accumulator.span().merge(argument.span());
                 accumulator = Expression {
-                    begin: accumulator.begin,
-                    end: argument.end,
+                    begin,
+                    end,
                     kind: ExpressionK::Application(Box::new(accumulator), vec![argument]),
                 };
                 continue;
@@ -328,13 +452,19 @@ impl<'a> Cursor<'a> {
             if let Token {
                 begin,
                 end,
-                kind: TokenK::Operator(OperatorK::Source),
+                kind: TokenK::Operator(OperatorK::Minus | OperatorK::Source),
                 ..
             } = self.peek()?
             {
-                let source_range = *begin..*end;
+                let source_range = begin..end;
                 let operator = SmolStr::new(&self.source[source_range]);
 
+                // `(a +)` is a left section, so the operator is left for the
+                // enclosing parentheses to take.
+                if let TokenK::CloseDelimiter(DelimiterK::Round) = self.peek_nth_kind(1)? {
+                    break;
+                }
+
                 let (left_power, right_power) = self.get_fixity(&operator)?;
 
                 if left_power < minimum_power {
@@ -344,9 +474,10 @@ impl<'a> Cursor<'a> {
                 }
 
                 let argument = self.expression_core(right_power)?;
+                let Span { begin, end } = accumulator.span().merge(argument.span());
                 accumulator = Expression {
-                    begin: accumulator.begin,
-                    end: argument.end,
+                    begin,
+                    end,
                     kind: ExpressionK::BinaryOperator(
                         Box::new(accumulator),
                         operator,
@@ -357,15 +488,16 @@ impl<'a> Cursor<'a> {
             }
 
             let argument = self.expression_atom()?;
+            let Span { begin, end } = accumulator.span().merge(argument.span());
             match &mut accumulator.kind {
                 ExpressionK::Application(_, arguments) => {
-                    accumulator.end = argument.end;
+                    accumulator.end = end;
                     arguments.push(argument);
                 }
                 _ => {
                     accumulator = Expression {
-                        begin: accumulator.begin,
-                        end: argument.end,
+                        begin,
+                        end,
                         kind: ExpressionK::Application(Box::new(accumulator), vec![argument]),
                     }
                 }
@@ -1,22 +1,28 @@
-use anyhow::Context;
-use lily_lexer::types::{DigitK, IdentifierK, LayoutK, Token, TokenK};
+use anyhow::{bail, Context};
+use lily_lexer::{
+    literal,
+    types::{DigitK, IdentifierK, LayoutK, OperatorK, Token, TokenK},
+};
 use smol_str::SmolStr;
 
 use crate::{
-    cursor::{expect_token, Cursor},
+    cursor::{expect_token, Cursor, KeywordContext},
     errors::ParseError,
     types::{Associativity, Domain, Fixity},
 };
 
 impl<'a> Cursor<'a> {
     pub fn fixity(&mut self) -> anyhow::Result<(SmolStr, Fixity)> {
+        self.context = KeywordContext::Fixity;
+
         let Token {
             begin: fixity_begin,
             kind,
             ..
         } = expect_token!(
             self,
-            TokenK::Identifier(IdentifierK::Infixl | IdentifierK::Infixr)
+            TokenK::Identifier(IdentifierK::Infixl),
+            TokenK::Identifier(IdentifierK::Infixr),
         );
         let associativity = match kind {
             TokenK::Identifier(IdentifierK::Infixl) => Associativity::Infixl,
@@ -25,33 +31,45 @@ impl<'a> Cursor<'a> {
         };
 
         let Token { begin, end, .. } = expect_token!(self, TokenK::Digit(DigitK::Int));
-        let binding_power = self.source[begin..end]
-            .parse()
-            .context(ParseError::InternalError(
-                "Malformed digit token.".to_string(),
-            ))?;
+        let binding_power = literal::integer(&self.source[begin..end])
+            .ok()
+            .and_then(|binding_power| u8::try_from(binding_power).ok())
+            .filter(|binding_power| *binding_power < u8::MAX)
+            .context(ParseError::IntegerOverflow(begin))?;
 
-        let (domain, identifier) = if let TokenK::Identifier(IdentifierK::Type) = self.peek()?.kind
-        {
-            self.take()?;
-            let Token { begin, end, .. } =
-                expect_token!(self, TokenK::Identifier(IdentifierK::Upper));
-            let identifier = SmolStr::new(&self.source[begin..end]);
-            (Domain::Type, identifier)
-        } else {
-            let Token { begin, end, .. } =
-                expect_token!(self, TokenK::Identifier(IdentifierK::Lower));
-            let identifier = SmolStr::new(&self.source[begin..end]);
-            (Domain::Value, identifier)
-        };
+        let (domain, identifier) =
+            if let TokenK::Identifier(IdentifierK::Type) = self.peek_kind()? {
+                self.take()?;
+                let Token { begin, end, .. } = expect_token!(
+                    self,
+                    TokenK::Identifier(IdentifierK::Upper),
+                    TokenK::Identifier(IdentifierK::QualifiedUpper),
+                );
+                let identifier = SmolStr::new(&self.source[begin..end]);
+                (Domain::Type, identifier)
+            } else {
+                let Token { begin, end, .. } = expect_token!(
+                    self,
+                    TokenK::Identifier(IdentifierK::Lower),
+                    TokenK::Identifier(IdentifierK::QualifiedLower),
+                );
+                let identifier = SmolStr::new(&self.source[begin..end]);
+                (Domain::Value, identifier)
+            };
 
         expect_token!(self, TokenK::Identifier(IdentifierK::As));
 
-        let Token {
+        let token @ Token {
             begin,
             end: fixity_end,
             ..
-        } = expect_token!(self, TokenK::Operator(_));
+        } = self.take()?;
+        if !token.is_operator() {
+            bail!(ParseError::UnexpectedToken(
+                token,
+                vec![TokenK::Operator(OperatorK::Source)]
+            ));
+        }
         let operator = SmolStr::new(&self.source[begin..fixity_end]);
 
         expect_token!(self, TokenK::Layout(LayoutK::Separator));
@@ -5,12 +5,12 @@ use smol_str::SmolStr;
 use crate::{
     cursor::{expect_token, Cursor},
     errors::ParseError,
-    types::{GreaterPattern, GreaterPatternK, LesserPattern, LesserPatternK},
+    types::{GreaterPattern, GreaterPatternK, HasSpan, LesserPattern, LesserPatternK, Span},
 };
 
 impl<'a> Cursor<'a> {
     pub fn lesser_pattern(&mut self) -> anyhow::Result<LesserPattern> {
-        if let TokenK::Operator(OperatorK::Underscore) = self.peek()?.kind {
+        if let TokenK::Operator(OperatorK::Underscore) = self.peek_kind()? {
             let Token { begin, end, .. } = self.take()?;
             return Ok(LesserPattern {
                 begin,
@@ -19,7 +19,7 @@ impl<'a> Cursor<'a> {
             });
         }
 
-        if let TokenK::Identifier(IdentifierK::Lower) = self.peek()?.kind {
+        if let TokenK::Identifier(IdentifierK::Lower) = self.peek_kind()? {
             let Token { begin, end, .. } = self.take()?;
             return Ok(LesserPattern {
                 begin,
@@ -28,13 +28,13 @@ impl<'a> Cursor<'a> {
             });
         }
 
-        bail!(ParseError::UnexpectedToken(self.peek()?.kind));
+        bail!(ParseError::UnexpectedToken(self.peek()?, vec![]));
     }
 
     pub fn lesser_patterns(&mut self) -> anyhow::Result<Vec<LesserPattern>> {
         let mut lesser_patterns = vec![];
         loop {
-            if let TokenK::Operator(OperatorK::Equal) = self.peek()?.kind {
+            if let TokenK::Operator(OperatorK::Equal) = self.peek_kind()? {
                 break Ok(lesser_patterns);
             }
 
@@ -43,18 +43,19 @@ impl<'a> Cursor<'a> {
                 continue;
             }
 
-            bail!(ParseError::UnexpectedToken(self.peek()?.kind));
+            bail!(ParseError::UnexpectedToken(self.peek()?, vec![]));
         }
     }
 }
 
 impl<'a> Cursor<'a> {
     fn greater_pattern_atom(&mut self) -> anyhow::Result<GreaterPattern> {
-        let Token {
+        let token @ Token {
             begin, end, kind, ..
         } = self.take()?;
 
         if let TokenK::Digit(DigitK::Int) = kind {
+            self.check_integer(token)?;
             return Ok(GreaterPattern {
                 begin,
                 end,
@@ -62,6 +63,14 @@ impl<'a> Cursor<'a> {
             });
         }
 
+        if let TokenK::Identifier(IdentifierK::True | IdentifierK::False) = kind {
+            return Ok(GreaterPattern {
+                begin,
+                end,
+                kind: GreaterPatternK::Boolean(kind == TokenK::Identifier(IdentifierK::True)),
+            });
+        }
+
         if let TokenK::Identifier(IdentifierK::Lower) = kind {
             return Ok(GreaterPattern {
                 begin,
@@ -70,7 +79,7 @@ impl<'a> Cursor<'a> {
             });
         }
 
-        if let TokenK::Identifier(IdentifierK::Upper) = kind {
+        if let TokenK::Identifier(IdentifierK::Upper | IdentifierK::QualifiedUpper) = kind {
             return Ok(GreaterPattern {
                 begin,
                 end,
@@ -96,7 +105,7 @@ impl<'a> Cursor<'a> {
             });
         }
 
-        bail!(ParseError::UnexpectedToken(kind));
+        bail!(ParseError::UnexpectedToken(token, vec![]));
     }
 
     fn greater_pattern_core(&mut self, minimum_power: u8) -> anyhow::Result<GreaterPattern> {
@@ -110,11 +119,11 @@ impl<'a> Cursor<'a> {
             if let Token {
                 begin,
                 end,
-                kind: TokenK::Operator(OperatorK::Source),
+                kind: TokenK::Operator(OperatorK::Minus | OperatorK::Source),
                 ..
             } = self.peek()?
             {
-                let source_range = *begin..*end;
+                let source_range = begin..end;
                 let operator = SmolStr::new(&self.source[source_range]);
 
                 let (left_power, right_power) = self.get_fixity(&operator)?;
@@ -126,9 +135,10 @@ impl<'a> Cursor<'a> {
                 }
 
                 let argument = self.greater_pattern_core(right_power)?;
+                let Span { begin, end } = accumulator.span().merge(argument.span());
                 accumulator = GreaterPattern {
-                    begin: accumulator.begin,
-                    end: argument.end,
+                    begin,
+                    end,
                     kind: GreaterPatternK::BinaryOperator(
                         Box::new(accumulator),
                         operator,
@@ -139,15 +149,16 @@ impl<'a> Cursor<'a> {
             }
 
             let argument = self.greater_pattern_atom()?;
+            let Span { begin, end } = accumulator.span().merge(argument.span());
             match &mut accumulator.kind {
                 GreaterPatternK::Application(_, arguments) => {
-                    accumulator.end = argument.end;
+                    accumulator.end = end;
                     arguments.push(argument);
                 }
                 _ => {
                     accumulator = GreaterPattern {
-                        begin: accumulator.begin,
-                        end: argument.end,
+                        begin,
+                        end,
                         kind: GreaterPatternK::Application(Box::new(accumulator), vec![argument]),
                     }
                 }
@@ -161,13 +172,13 @@ impl<'a> Cursor<'a> {
         let mut greater_patterns = vec![];
 
         loop {
-            if let TokenK::Operator(OperatorK::Comma) = self.peek()?.kind {
+            if let TokenK::Operator(OperatorK::Comma) = self.peek_kind()? {
                 expect_token!(self, TokenK::Operator(OperatorK::Comma));
                 continue;
             }
 
             if let TokenK::Identifier(IdentifierK::If) | TokenK::Operator(OperatorK::ArrowRight) =
-                self.peek()?.kind
+                self.peek_kind()?
             {
                 break;
             }
@@ -3,10 +3,10 @@ use lily_lexer::types::{DelimiterK, IdentifierK, OperatorK, Token, TokenK};
 use smol_str::SmolStr;
 
 use crate::{
-    cursor::Cursor,
+    cursor::{Cursor, KeywordContext},
     errors::ParseError,
     expect_token,
-    types::{Ty, TyK},
+    types::{HasSpan, Span, Ty, TyK},
 };
 
 impl<'a> Cursor<'a> {
@@ -15,7 +15,7 @@ impl<'a> Cursor<'a> {
             begin, end, kind, ..
         } = self.take()?;
 
-        if let TokenK::Identifier(IdentifierK::Upper) = kind {
+        if let TokenK::Identifier(IdentifierK::Upper | IdentifierK::QualifiedUpper) = kind {
             return Ok(Ty {
                 begin,
                 end,
@@ -23,6 +23,29 @@ impl<'a> Cursor<'a> {
             });
         }
 
+        // `forall` binds variables over the type that follows the period.
+        if let TokenK::Identifier(IdentifierK::Forall) = kind {
+            let mut variables = vec![];
+            while let TokenK::Identifier(IdentifierK::Lower) = self.peek_kind()? {
+                let Token { begin, end, .. } = self.take()?;
+                variables.push(SmolStr::new(&self.source[begin..end]));
+            }
+            if variables.is_empty() {
+                bail!(ParseError::UnexpectedToken(
+                    self.take()?,
+                    vec![TokenK::Identifier(IdentifierK::Lower)]
+                ));
+            }
+            expect_token!(self, TokenK::Operator(OperatorK::Period));
+            let ty = self.ty_constrained()?;
+            let Span { begin, end } = Span::from(token).merge(ty.span());
+            return Ok(Ty {
+                begin,
+                end,
+                kind: TyK::Forall(variables, Box::new(ty)),
+            });
+        }
+
         if let TokenK::Identifier(IdentifierK::Lower) = kind {
             return Ok(Ty {
                 begin,
@@ -32,7 +55,7 @@ impl<'a> Cursor<'a> {
         }
 
         if let TokenK::OpenDelimiter(DelimiterK::Round) = kind {
-            let ty = self.ty_core(0)?;
+            let ty = self.ty_constrained()?;
             let Token { end, .. } = expect_token!(self, TokenK::CloseDelimiter(DelimiterK::Round));
             return Ok(Ty {
                 begin,
@@ -41,7 +64,7 @@ impl<'a> Cursor<'a> {
             });
         }
 
-        bail!(ParseError::UnexpectedToken(token.kind));
+        bail!(ParseError::UnexpectedToken(token, vec![]));
     }
 
     fn ty_core(&mut self, minimum_power: u8) -> anyhow::Result<Ty> {
@@ -59,7 +82,7 @@ impl<'a> Cursor<'a> {
                 ..
             } = self.peek()?
             {
-                let source_range = *begin..*end;
+                let source_range = begin..end;
                 let operator = SmolStr::new(&self.source[source_range]);
 
                 let (left_power, right_power) = self.get_type_fixity(&operator)?;
@@ -71,24 +94,26 @@ impl<'a> Cursor<'a> {
                 }
 
                 let argument = self.ty_core(right_power)?;
+                let Span { begin, end } = accumulator.span().merge(argument.span());
                 accumulator = Ty {
-                    begin: accumulator.begin,
-                    end: argument.end,
+                    begin,
+                    end,
                     kind: TyK::BinaryOperator(Box::new(accumulator), operator, Box::new(argument)),
                 };
                 continue;
             }
 
             let argument = self.ty_atom()?;
+            let Span { begin, end } = accumulator.span().merge(argument.span());
             match &mut accumulator.kind {
                 TyK::Application(_, arguments) => {
-                    accumulator.end = argument.end;
+                    accumulator.end = end;
                     arguments.push(argument);
                 }
                 _ => {
                     accumulator = Ty {
-                        begin: accumulator.begin,
-                        end: accumulator.end,
+                        begin,
+                        end,
                         kind: TyK::Application(Box::new(accumulator), vec![argument]),
                     }
                 }
@@ -98,7 +123,26 @@ impl<'a> Cursor<'a> {
         Ok(accumulator)
     }
 
+    // Constraints bind looser than any type operator, and nest to the right.
+    fn ty_constrained(&mut self) -> anyhow::Result<Ty> {
+        let ty = self.ty_core(0)?;
+        if let TokenK::Operator(OperatorK::ArrowConstraint) = self.peek_kind()? {
+            self.take()?;
+            let constrained = self.ty_constrained()?;
+            let Span { begin, end } = ty.span().merge(constrained.span());
+            return Ok(Ty {
+                begin,
+                end,
+                kind: TyK::Constrained(Box::new(ty), Box::new(constrained)),
+            });
+        }
+        Ok(ty)
+    }
+
     pub fn ty(&mut self) -> anyhow::Result<Ty> {
-        self.ty_core(0)
+        let context = std::mem::replace(&mut self.context, KeywordContext::Type);
+        let ty = self.ty_constrained();
+        self.context = context;
+        ty
     }
 }
@@ -1,15 +1,50 @@
 use anyhow::{bail, Context};
-use lily_lexer::types::Token;
+use lily_lexer::{
+    literal::{self, IntegerError},
+    types::{IdentifierK, LayoutK, OperatorK, Token, TokenK},
+};
 use smol_str::SmolStr;
 
-use crate::{errors::ParseError, types::FixityMap};
+use crate::{
+    errors::ParseError,
+    types::{Fixity, FixityMap},
+};
+
+// Operators without a declared fixity are treated as `infixl 9`.
+const DEFAULT_FIXITY: (u8, u8) = (9, 10);
+
+// Contextual keywords are always lexed as keywords, and only keep that
+// meaning within the syntax that uses them. Elsewhere, they're resolved
+// to plain names such that the lexer can stay context-free.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordContext {
+    Declaration,
+    Fixity,
+    Type,
+}
+
+pub fn resolve_keyword(kind: TokenK, context: KeywordContext) -> TokenK {
+    match (kind, context) {
+        (TokenK::Identifier(IdentifierK::As), KeywordContext::Fixity) => kind,
+        (TokenK::Identifier(IdentifierK::Forall), KeywordContext::Type) => kind,
+        (TokenK::Operator(OperatorK::ArrowConstraint), KeywordContext::Type) => kind,
+        (TokenK::Identifier(IdentifierK::As | IdentifierK::Forall), _) => {
+            TokenK::Identifier(IdentifierK::Lower)
+        }
+        (TokenK::Operator(OperatorK::ArrowConstraint), _) => TokenK::Operator(OperatorK::Source),
+        _ => kind,
+    }
+}
 
 pub struct Cursor<'a> {
     pub source: &'a str,
     tokens: &'a [Token],
     index: usize,
+    pub context: KeywordContext,
     value_fixities: Option<&'a FixityMap>,
     type_fixities: Option<&'a FixityMap>,
+    recovering: bool,
+    pub errors: Vec<ParseError>,
 }
 
 impl<'a> Cursor<'a> {
@@ -23,27 +58,57 @@ impl<'a> Cursor<'a> {
             source,
             tokens,
             index: 0,
+            context: KeywordContext::Declaration,
             value_fixities,
             type_fixities,
+            recovering: false,
+            errors: vec![],
+        }
+    }
+
+    pub fn recovering(mut self) -> Self {
+        self.recovering = true;
+        self
+    }
+
+    fn resolve(&self, token: Token) -> Token {
+        Token {
+            kind: resolve_keyword(token.kind, self.context),
+            ..token
         }
     }
 
-    pub fn peek(&mut self) -> anyhow::Result<&Token> {
+    pub fn peek(&mut self) -> anyhow::Result<Token> {
         if self.is_eof() {
-            bail!(ParseError::UnexpectedEndOfFile);
+            bail!(ParseError::UnexpectedEndOfFile(self.end_offset()));
+        }
+        Ok(self.resolve(self.tokens[self.index]))
+    }
+
+    pub fn peek_kind(&mut self) -> anyhow::Result<TokenK> {
+        Ok(self.peek()?.kind)
+    }
+
+    pub fn peek_nth_kind(&mut self, n: usize) -> anyhow::Result<TokenK> {
+        match self.tokens.get(self.index + n) {
+            Some(token) => Ok(resolve_keyword(token.kind, self.context)),
+            None => bail!(ParseError::UnexpectedEndOfFile(self.end_offset())),
         }
-        Ok(&self.tokens[self.index])
     }
 
     pub fn take(&mut self) -> anyhow::Result<Token> {
         if self.is_eof() {
-            bail!(ParseError::UnexpectedEndOfFile);
+            bail!(ParseError::UnexpectedEndOfFile(self.end_offset()));
         }
-        let token = self.tokens[self.index];
+        let token = self.resolve(self.tokens[self.index]);
         self.index += 1;
         Ok(token)
     }
 
+    fn end_offset(&self) -> usize {
+        self.tokens.last().map_or(0, |token| token.end)
+    }
+
     pub fn is_eof(&mut self) -> bool {
         self.index == self.tokens.len()
     }
@@ -62,34 +127,89 @@ impl<'a> Cursor<'a> {
         }
     }
 
+    // Integer literals are kept as written, but still have to fit in 64 bits.
+    pub fn check_integer(&self, Token { begin, end, .. }: Token) -> anyhow::Result<()> {
+        if let Err(IntegerError::Overflow) = literal::integer(&self.source[begin..end]) {
+            bail!(ParseError::IntegerOverflow(begin));
+        }
+        Ok(())
+    }
+
+    // When recovering, an error within a block item is recorded, and the
+    // cursor skips to the separator that ends the item, or to the end of
+    // the block that contains it.
+    pub fn recover<T>(
+        &mut self,
+        callback: impl FnOnce(&mut Self) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Option<T>> {
+        if !self.recovering {
+            return callback(self).map(Some);
+        }
+        let index = self.index;
+        let depth = self.peek()?.depth;
+        match callback(self) {
+            Ok(item) => Ok(Some(item)),
+            Err(error) => {
+                self.errors.push(error.into());
+                self.index = index;
+                self.sync_to(depth);
+                Ok(None)
+            }
+        }
+    }
+
+    fn sync_to(&mut self, depth: usize) {
+        while let Some(token) = self.tokens.get(self.index) {
+            match token.kind {
+                TokenK::Layout(LayoutK::Separator) if token.depth == depth => {
+                    self.index += 1;
+                    break;
+                }
+                TokenK::Layout(LayoutK::End) if token.depth == depth => break,
+                _ => self.index += 1,
+            }
+        }
+    }
+
+    // A block that lost all of its items can't be represented, so its last
+    // error is raised again for the enclosing item to recover from.
+    pub fn non_empty<T>(&mut self, items: Vec<T>) -> anyhow::Result<Vec<T>> {
+        if items.is_empty() {
+            if let Some(error) = self.errors.pop() {
+                bail!(error);
+            }
+        }
+        Ok(items)
+    }
+
     pub fn get_fixity(&self, operator: &SmolStr) -> anyhow::Result<(u8, u8)> {
         Ok(self
             .value_fixities
             .context(ParseError::UnknownBindingPower(operator.clone()))?
             .get(operator)
-            .context(ParseError::UnknownBindingPower(operator.clone()))?
-            .as_pair())
+            .map_or(DEFAULT_FIXITY, Fixity::as_pair))
     }
 
+    // Type operators have no default, as an undeclared `->` would otherwise
+    // silently associate to the left.
     pub fn get_type_fixity(&self, operator: &SmolStr) -> anyhow::Result<(u8, u8)> {
-        Ok(self
-            .type_fixities
-            .context(ParseError::UnknownBindingPower(operator.clone()))?
-            .get(operator)
-            .context(ParseError::UnknownBindingPower(operator.clone()))?
-            .as_pair())
+        self.type_fixities
+            .and_then(|fixities| fixities.get(operator))
+            .map(Fixity::as_pair)
+            .context(ParseError::UnknownBindingPower(operator.clone()))
     }
 }
 
 #[doc(hidden)]
 #[macro_export]
 macro_rules! expect_token {
-    ($self:ident, $kind:pat) => {{
+    ($self:ident, $($kind:expr),+ $(,)?) => {{
         let token = $self.take()?;
-        if matches!(token.kind, $kind) {
+        let expected = [$($kind),+];
+        if expected.contains(&token.kind) {
             token
         } else {
-            anyhow::bail!(ParseError::UnexpectedToken(token.kind));
+            anyhow::bail!(ParseError::UnexpectedToken(token, expected.to_vec()));
         }
     }};
 }
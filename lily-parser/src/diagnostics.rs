@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+
+use lily_lexer::types::{Token, TokenK};
+
+use crate::{errors::ParseError, types::Span};
+
+// Entries are kept ordered by their span, then by their message, and
+// identical entries are only reported once.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Diagnostics {
+    entries: BTreeSet<(Span, String)>,
+}
+
+impl Diagnostics {
+    pub fn push(&mut self, span: Span, message: impl Into<String>) {
+        self.entries.insert((span, message.into()));
+    }
+
+    pub fn push_token(&mut self, token: &Token) {
+        if let TokenK::Unknown(kind) = token.kind {
+            if !token.is_eof() {
+                let span = Span {
+                    begin: token.begin,
+                    end: token.end,
+                };
+                self.push(span, kind.to_string());
+            }
+        }
+    }
+
+    // Errors that can't be traced back to a location are reported at the
+    // beginning of the file, while unexpected error tokens are left to
+    // `push_token`, as the lexer has already reported them.
+    pub fn push_error(&mut self, error: &ParseError) {
+        let span = match error {
+            ParseError::UnexpectedToken(token, _) if token.is_error() => return,
+            ParseError::UnexpectedToken(token, _) => Span {
+                begin: token.begin,
+                end: token.end,
+            },
+            ParseError::UnexpectedEndOfFile(offset) | ParseError::IntegerOverflow(offset) => Span {
+                begin: *offset,
+                end: *offset,
+            },
+            _ => Span { begin: 0, end: 0 },
+        };
+        self.push(span, error.to_string());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(Span, String)> {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lily_lexer::lex;
+
+    use super::Diagnostics;
+    use crate::{parse_top_level_recovering, types::Span};
+
+    #[test]
+    fn sorted_and_deduplicated() {
+        let mut diagnostics = Diagnostics::default();
+        diagnostics.push(Span { begin: 8, end: 9 }, "c");
+        diagnostics.push(Span { begin: 2, end: 6 }, "b");
+        diagnostics.push(Span { begin: 2, end: 4 }, "a");
+        diagnostics.push(Span { begin: 8, end: 9 }, "c");
+        let entries: Vec<_> = diagnostics
+            .iter()
+            .map(|(span, message)| (span.begin, span.end, message.as_str()))
+            .collect();
+        assert_eq!(entries, vec![(2, 4, "a"), (2, 6, "b"), (8, 9, "c")]);
+    }
+
+    #[test]
+    fn lexer_and_parser_errors() {
+        let source = "example = \"a\n\nexample = )";
+        let mut diagnostics = Diagnostics::default();
+        parse_top_level_recovering(source, Default::default())
+            .1
+            .iter()
+            .for_each(|error| diagnostics.push_error(error));
+        lex(source)
+            .iter()
+            .for_each(|token| diagnostics.push_token(token));
+        let entries: Vec<_> = diagnostics
+            .iter()
+            .map(|(span, message)| (span.begin, message.as_str()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (10, "unterminated string literal"),
+                (24, "Unexpected `)` at offset 24."),
+            ]
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::types::{
+    CaseArm, Declaration, DeclarationK, DoStatement, DoStatementK, Expression, ExpressionK,
+    GreaterPattern, GreaterPatternK, LesserPattern, LesserPatternK, Ty, TyK,
+};
+
+fn separated<T: Display>(f: &mut Formatter<'_>, items: &[T], separator: &str) -> fmt::Result {
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            write!(f, "{}", separator)?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+impl Display for LesserPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl Display for LesserPatternK {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LesserPatternK::Null => write!(f, "_"),
+            LesserPatternK::Variable(variable) => write!(f, "{}", variable),
+        }
+    }
+}
+
+impl Display for GreaterPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl Display for GreaterPatternK {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GreaterPatternK::Application(head, arguments) => {
+                write!(f, "{} ", head)?;
+                separated(f, arguments, " ")
+            }
+            GreaterPatternK::BinaryOperator(left, operator, right) => {
+                write!(f, "{} {} {}", left, operator, right)
+            }
+            GreaterPatternK::Boolean(boolean) => write!(f, "{}", boolean),
+            GreaterPatternK::Constructor(constructor) => write!(f, "{}", constructor),
+            GreaterPatternK::Integer(integer) => write!(f, "{}", integer),
+            GreaterPatternK::Null => write!(f, "_"),
+            GreaterPatternK::Parenthesized(pattern) => write!(f, "({})", pattern),
+            GreaterPatternK::Variable(variable) => write!(f, "{}", variable),
+        }
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+// Blocks are rendered with explicit braces and semicolons, since the
+// layout of the original source is not retained.
+impl Display for ExpressionK {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpressionK::Application(head, arguments) => {
+                write!(f, "{} ", head)?;
+                separated(f, arguments, " ")
+            }
+            ExpressionK::Array(elements) => {
+                write!(f, "[")?;
+                separated(f, elements, ", ")?;
+                write!(f, "]")
+            }
+            ExpressionK::BinaryOperator(left, operator, right) => {
+                write!(f, "{} {} {}", left, operator, right)
+            }
+            ExpressionK::Boolean(boolean) => write!(f, "{}", boolean),
+            ExpressionK::CaseOf(expressions, arms) => {
+                write!(f, "case ")?;
+                separated(f, expressions, ", ")?;
+                write!(f, " of {{ ")?;
+                separated(f, arms, "; ")?;
+                write!(f, " }}")
+            }
+            ExpressionK::Constructor(constructor) => write!(f, "{}", constructor),
+            ExpressionK::DoBlock(statements) => {
+                write!(f, "do {{ ")?;
+                separated(f, statements, "; ")?;
+                write!(f, " }}")
+            }
+            ExpressionK::Float(float) => write!(f, "{}", float),
+            ExpressionK::IfThenElse(condition, then_value, else_value) => {
+                write!(
+                    f,
+                    "if {} then {} else {}",
+                    condition, then_value, else_value
+                )
+            }
+            ExpressionK::Integer(integer) => write!(f, "{}", integer),
+            ExpressionK::Lambda(arguments, expression) => {
+                write!(f, "\\")?;
+                separated(f, arguments, " ")?;
+                write!(f, " -> {}", expression)
+            }
+            ExpressionK::LeftSection(expression, operator) => {
+                write!(f, "({} {})", expression, operator)
+            }
+            ExpressionK::Let(declarations, expression) => {
+                write!(f, "let {{ ")?;
+                separated(f, declarations, "; ")?;
+                write!(f, " }} in {}", expression)
+            }
+            ExpressionK::Parenthesized(expression) => write!(f, "({})", expression),
+            ExpressionK::Record(fields) => {
+                if fields.is_empty() {
+                    return write!(f, "{{}}");
+                }
+                write!(f, "{{ ")?;
+                for (index, (name, expression)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{} = {}", name, expression)?;
+                }
+                write!(f, " }}")
+            }
+            ExpressionK::RightSection(operator, expression) => {
+                write!(f, "({} {})", operator, expression)
+            }
+            ExpressionK::Variable(variable) => write!(f, "{}", variable),
+        }
+    }
+}
+
+impl Display for DoStatement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl Display for DoStatementK {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DoStatementK::BindExpression(pattern, expression) => {
+                write!(f, "{} <- {}", pattern, expression)
+            }
+            DoStatementK::DiscardExpression(expression) => write!(f, "{}", expression),
+            DoStatementK::LetStatement(declarations) => {
+                write!(f, "let {{ ")?;
+                separated(f, declarations, "; ")?;
+                write!(f, " }}")
+            }
+        }
+    }
+}
+
+impl Display for CaseArm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        separated(f, &self.patterns, ", ")?;
+        if let Some(condition) = &self.condition {
+            write!(f, " if {}", condition)?;
+        }
+        write!(f, " -> {}", self.expression)
+    }
+}
+
+impl Display for Ty {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl Display for TyK {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TyK::Application(head, arguments) => {
+                write!(f, "{} ", head)?;
+                separated(f, arguments, " ")
+            }
+            TyK::BinaryOperator(left, operator, right) => {
+                write!(f, "{} {} {}", left, operator, right)
+            }
+            TyK::Constrained(constraint, ty) => write!(f, "{} => {}", constraint, ty),
+            TyK::Constructor(constructor) => write!(f, "{}", constructor),
+            TyK::Forall(variables, ty) => {
+                write!(f, "forall ")?;
+                separated(f, variables, " ")?;
+                write!(f, ". {}", ty)
+            }
+            TyK::Parenthesized(ty) => write!(f, "({})", ty),
+            TyK::Variable(variable) => write!(f, "{}", variable),
+        }
+    }
+}
+
+impl Display for Declaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl Display for DeclarationK {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DeclarationK::ValueDeclaration(identifier, patterns, expression) => {
+                write!(f, "{}", identifier)?;
+                for pattern in patterns {
+                    write!(f, " {}", pattern)?;
+                }
+                write!(f, " = {}", expression)
+            }
+            DeclarationK::TypeDeclaration(identifier, ty) => write!(f, "{} : {}", identifier, ty),
+        }
+    }
+}
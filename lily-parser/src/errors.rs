@@ -1,15 +1,38 @@
-use lily_lexer::types::TokenK;
+use lily_lexer::types::{Token, TokenK};
 use smol_str::SmolStr;
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum ParseError {
-    #[error("Unexpected end of file.")]
-    UnexpectedEndOfFile,
-    #[error("Unexpected token {0:?}.")]
-    UnexpectedToken(TokenK),
+    #[error("Unexpected end of file at offset {0}.")]
+    UnexpectedEndOfFile(usize),
+    #[error("Unexpected {} at offset {}{}.", .0.kind, .0.begin, expected(.1))]
+    UnexpectedToken(Token, Vec<TokenK>),
     #[error("Unknown binding power for operator {0:?}.")]
     UnknownBindingPower(SmolStr),
+    #[error("Integer literal at offset {0} is out of range.")]
+    IntegerOverflow(usize),
     #[error("Internal error: {0}. This incident should be reported!")]
     InternalError(String),
 }
+
+// Parsing functions raise `ParseError`s through `anyhow`, so anything else
+// reaching the caller is a bug in the parser itself.
+impl From<anyhow::Error> for ParseError {
+    fn from(error: anyhow::Error) -> Self {
+        error
+            .downcast()
+            .unwrap_or_else(|error: anyhow::Error| ParseError::InternalError(error.to_string()))
+    }
+}
+
+fn expected(kinds: &[TokenK]) -> String {
+    match kinds {
+        [] => String::new(),
+        [kind] => format!(", expected {}", kind),
+        [kinds @ .., last] => {
+            let kinds: Vec<String> = kinds.iter().map(ToString::to_string).collect();
+            format!(", expected {} or {}", kinds.join(", "), last)
+        }
+    }
+}
@@ -1,6 +1,8 @@
 mod core;
 mod cursor;
-mod errors;
+pub mod diagnostics;
+mod display;
+pub mod errors;
 pub mod types;
 
 use lily_lexer::{lex, types::Token};
@@ -8,10 +10,28 @@ use types::Module;
 
 use crate::{
     cursor::Cursor,
-    types::{Domain, FixityMap},
+    errors::ParseError,
+    types::{Domain, FixityTable},
 };
 
 pub fn parse_top_level(source: &str) -> anyhow::Result<Module> {
+    parse_top_level_with(source, FixityTable::default())
+}
+
+pub fn parse_fixities(source: &str) -> anyhow::Result<FixityTable> {
+    let tokens = lex(source);
+    let mut fixities = FixityTable::default();
+    for group in partition(&tokens) {
+        if group.first().unwrap().is_infix_identifier() {
+            insert_fixity(source, group, &mut fixities)?;
+        }
+    }
+    Ok(fixities)
+}
+
+// Fixities declared in the source take precedence over the ones in the
+// table, which can be used to provide operators from elsewhere.
+pub fn parse_top_level_with(source: &str, mut fixities: FixityTable) -> anyhow::Result<Module> {
     let tokens = lex(source);
 
     let mut fixity_groups = vec![];
@@ -25,17 +45,8 @@ pub fn parse_top_level(source: &str) -> anyhow::Result<Module> {
         }
     }
 
-    let mut value_fixities = FixityMap::default();
-    let mut type_fixities = FixityMap::default();
-
     for fixity_group in fixity_groups {
-        let mut cursor = Cursor::new(source, fixity_group, None, None);
-        let (operator, fixity) = cursor.fixity()?;
-        match fixity.domain {
-            Domain::Type => type_fixities.insert(operator, fixity),
-            Domain::Value => value_fixities.insert(operator, fixity),
-        };
-        debug_assert!(cursor.is_eof());
+        insert_fixity(source, fixity_group, &mut fixities)?;
     }
 
     let mut declarations = vec![];
@@ -43,8 +54,8 @@ pub fn parse_top_level(source: &str) -> anyhow::Result<Module> {
         let mut cursor = Cursor::new(
             source,
             declaration_group,
-            Some(&value_fixities),
-            Some(&type_fixities),
+            Some(&fixities.values),
+            Some(&fixities.types),
         );
         declarations.push(cursor.declaration()?);
         debug_assert!(cursor.is_eof());
@@ -53,6 +64,53 @@ pub fn parse_top_level(source: &str) -> anyhow::Result<Module> {
     Ok(Module { declarations })
 }
 
+// Top-level groups are independent of each other, so an error only
+// discards the declaration it occurs in, and parsing resumes with the
+// next group. Within a declaration, errors in the items of a block only
+// discard the item. Fixities are collected first, as before.
+pub fn parse_top_level_recovering(
+    source: &str,
+    mut fixities: FixityTable,
+) -> (Module, Vec<ParseError>) {
+    let tokens = lex(source);
+    let groups: Vec<&[Token]> = partition(&tokens).collect();
+    let mut errors = vec![];
+
+    for group in groups.iter().filter(|group| group[0].is_infix_identifier()) {
+        if let Err(error) = insert_fixity(source, group, &mut fixities) {
+            errors.push(error.into());
+        }
+    }
+
+    let mut declarations = vec![];
+    for group in groups
+        .iter()
+        .filter(|group| !group[0].is_infix_identifier())
+    {
+        let mut cursor =
+            Cursor::new(source, group, Some(&fixities.values), Some(&fixities.types)).recovering();
+        let declaration = cursor.declaration();
+        errors.append(&mut cursor.errors);
+        match declaration {
+            Ok(declaration) => declarations.push(declaration),
+            Err(error) => errors.push(error.into()),
+        }
+    }
+
+    (Module { declarations }, errors)
+}
+
+fn insert_fixity(source: &str, group: &[Token], fixities: &mut FixityTable) -> anyhow::Result<()> {
+    let mut cursor = Cursor::new(source, group, None, None);
+    let (operator, fixity) = cursor.fixity()?;
+    match fixity.domain {
+        Domain::Type => fixities.types.insert(operator, fixity),
+        Domain::Value => fixities.values.insert(operator, fixity),
+    };
+    debug_assert!(cursor.is_eof());
+    Ok(())
+}
+
 fn partition(tokens: &[Token]) -> impl Iterator<Item = &[Token]> {
     let mut tokens_iter = tokens.iter();
     let mut last_start = 0;
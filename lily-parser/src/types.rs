@@ -1,3 +1,4 @@
+use lily_lexer::types::Token;
 use rustc_hash::FxHashMap;
 use smol_str::SmolStr;
 
@@ -34,6 +35,152 @@ impl Fixity {
 
 pub type FixityMap = FxHashMap<SmolStr, Fixity>;
 
+#[derive(Debug, Default)]
+pub struct FixityTable {
+    pub values: FixityMap,
+    pub types: FixityMap,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    pub begin: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn merge(self, other: Span) -> Span {
+        Span {
+            begin: self.begin.min(other.begin),
+            end: self.end.max(other.end),
+        }
+    }
+
+    pub fn contains(&self, offset: usize) -> bool {
+        self.begin <= offset && offset < self.end
+    }
+
+    pub fn length(&self) -> usize {
+        self.end - self.begin
+    }
+}
+
+impl From<Token> for Span {
+    fn from(Token { begin, end, .. }: Token) -> Span {
+        Span { begin, end }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Spanned<T> {
+    pub begin: usize,
+    pub end: usize,
+    pub node: T,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(Span { begin, end }: Span, node: T) -> Self {
+        Self { begin, end, node }
+    }
+
+    pub fn span(&self) -> Span {
+        Span {
+            begin: self.begin,
+            end: self.end,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Spanned<U> {
+        Spanned {
+            begin: self.begin,
+            end: self.end,
+            node: f(self.node),
+        }
+    }
+}
+
+pub trait HasSpan {
+    fn begin(&self) -> usize;
+
+    fn end(&self) -> usize;
+
+    fn span(&self) -> Span {
+        Span {
+            begin: self.begin(),
+            end: self.end(),
+        }
+    }
+
+    fn contains(&self, offset: usize) -> bool {
+        self.span().contains(offset)
+    }
+
+    fn length(&self) -> usize {
+        self.span().length()
+    }
+}
+
+impl<T> HasSpan for Spanned<T> {
+    fn begin(&self) -> usize {
+        self.begin
+    }
+
+    fn end(&self) -> usize {
+        self.end
+    }
+}
+
+macro_rules! impl_span_from {
+    ($($node:ident => $kind:ty),+) => {
+        $(impl HasSpan for $node {
+            fn begin(&self) -> usize {
+                self.begin
+            }
+
+            fn end(&self) -> usize {
+                self.end
+            }
+        }
+
+        impl From<&$node> for Span {
+            fn from(node: &$node) -> Span {
+                Span {
+                    begin: node.begin,
+                    end: node.end,
+                }
+            }
+        }
+
+        impl From<$node> for Spanned<$kind> {
+            fn from($node { begin, end, kind }: $node) -> Spanned<$kind> {
+                Spanned {
+                    begin,
+                    end,
+                    node: kind,
+                }
+            }
+        }
+
+        impl From<Spanned<$kind>> for $node {
+            fn from(Spanned { begin, end, node }: Spanned<$kind>) -> $node {
+                $node {
+                    begin,
+                    end,
+                    kind: node,
+                }
+            }
+        })+
+    };
+}
+
+impl_span_from!(
+    LesserPattern => LesserPatternK,
+    GreaterPattern => GreaterPatternK,
+    Expression => ExpressionK,
+    DoStatement => DoStatementK,
+    Ty => TyK,
+    Declaration => DeclarationK
+);
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct LesserPattern {
     pub begin: usize,
@@ -58,6 +205,7 @@ pub struct GreaterPattern {
 pub enum GreaterPatternK {
     Application(Box<GreaterPattern>, Vec<GreaterPattern>),
     BinaryOperator(Box<GreaterPattern>, SmolStr, Box<GreaterPattern>),
+    Boolean(bool),
     Constructor(SmolStr),
     Integer(SmolStr),
     Null,
@@ -75,15 +223,21 @@ pub struct Expression {
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ExpressionK {
     Application(Box<Expression>, Vec<Expression>),
+    Array(Vec<Expression>),
     BinaryOperator(Box<Expression>, SmolStr, Box<Expression>),
+    Boolean(bool),
     CaseOf(Vec<Expression>, Vec<CaseArm>),
     Constructor(SmolStr),
     DoBlock(Vec<DoStatement>),
     Float(SmolStr),
     IfThenElse(Box<Expression>, Box<Expression>, Box<Expression>),
     Integer(SmolStr),
+    Lambda(Vec<LesserPattern>, Box<Expression>),
+    LeftSection(Box<Expression>, SmolStr),
     Let(Vec<Declaration>, Box<Expression>),
     Parenthesized(Box<Expression>),
+    Record(Vec<(SmolStr, Expression)>),
+    RightSection(SmolStr, Box<Expression>),
     Variable(SmolStr),
 }
 
@@ -119,7 +273,9 @@ pub struct Ty {
 pub enum TyK {
     Application(Box<Ty>, Vec<Ty>),
     BinaryOperator(Box<Ty>, SmolStr, Box<Ty>),
+    Constrained(Box<Ty>, Box<Ty>),
     Constructor(SmolStr),
+    Forall(Vec<SmolStr>, Box<Ty>),
     Parenthesized(Box<Ty>),
     Variable(SmolStr),
 }
@@ -141,3 +297,86 @@ pub enum DeclarationK {
 pub struct Module {
     pub declarations: Vec<Declaration>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Expression, ExpressionK, GreaterPattern, GreaterPatternK, HasSpan, LesserPattern,
+        LesserPatternK, Span, Spanned,
+    };
+
+    #[test]
+    fn merge_spans() {
+        let a = Span { begin: 2, end: 4 };
+        let b = Span { begin: 8, end: 10 };
+        let c = Span { begin: 3, end: 9 };
+        assert_eq!(a.merge(b), Span { begin: 2, end: 10 });
+        assert_eq!(b.merge(a), Span { begin: 2, end: 10 });
+        assert_eq!(a.merge(b).merge(c), Span { begin: 2, end: 10 });
+        assert_eq!(c.merge(Span { begin: 4, end: 5 }), c);
+    }
+
+    #[test]
+    fn span_from_expression() {
+        let expression = Expression {
+            begin: 11,
+            end: 18,
+            kind: ExpressionK::Constructor("Nothing".into()),
+        };
+        assert_eq!(Span::from(&expression), Span { begin: 11, end: 18 });
+    }
+
+    #[test]
+    fn span_contains_and_length() {
+        let span = Span { begin: 2, end: 5 };
+        assert!(!span.contains(1));
+        assert!(span.contains(2));
+        assert!(span.contains(4));
+        assert!(!span.contains(5));
+        assert_eq!(span.length(), 3);
+        assert_eq!(Span { begin: 5, end: 5 }.length(), 0);
+    }
+
+    #[test]
+    fn has_span_boundaries() {
+        let lesser_pattern = LesserPattern {
+            begin: 4,
+            end: 5,
+            kind: LesserPatternK::Null,
+        };
+        assert!(!lesser_pattern.contains(3));
+        assert!(lesser_pattern.contains(4));
+        assert!(!lesser_pattern.contains(5));
+        assert_eq!(lesser_pattern.length(), 1);
+
+        let greater_pattern = GreaterPattern {
+            begin: 0,
+            end: 7,
+            kind: GreaterPatternK::Constructor("Nothing".into()),
+        };
+        assert!(greater_pattern.contains(0));
+        assert!(greater_pattern.contains(6));
+        assert!(!greater_pattern.contains(7));
+        assert_eq!(greater_pattern.span(), Span { begin: 0, end: 7 });
+    }
+
+    #[test]
+    fn spanned_expression() {
+        let expression = Expression {
+            begin: 11,
+            end: 18,
+            kind: ExpressionK::Constructor("Nothing".into()),
+        };
+        let spanned = Spanned::from(expression);
+        assert_eq!(spanned.span(), Span { begin: 11, end: 18 });
+        assert_eq!(spanned.node, ExpressionK::Constructor("Nothing".into()));
+        assert_eq!(
+            Expression::from(spanned.map(|_| ExpressionK::Variable("x".into()))),
+            Expression {
+                begin: 11,
+                end: 18,
+                kind: ExpressionK::Variable("x".into()),
+            }
+        );
+    }
+}
@@ -1,4 +1,9 @@
-use lily_parser::parse_top_level;
+use lily_lexer::types::{DelimiterK, LayoutK, Token, TokenK};
+use lily_parser::{
+    errors::ParseError,
+    parse_fixities, parse_top_level, parse_top_level_recovering, parse_top_level_with,
+    types::{DeclarationK, ExpressionK, Module},
+};
 
 #[test]
 pub fn top_level_0() {
@@ -122,3 +127,504 @@ example : A a -> B -> C -> D (d e)
 ";
     insta::assert_debug_snapshot!(parse_top_level(source));
 }
+
+#[test]
+fn top_level_11() {
+    let source = "
+infixl 6 sub as -
+
+example = a-1 - b
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_12() {
+    let source = "
+example = Nothing
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_13() {
+    let source = "
+infixr 5 cons as ::
+
+example = case a of
+  Just (Left x) -> x
+  x :: xs -> x
+  _ -> b
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_14() {
+    let source = "
+example _ x = x
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_15() {
+    let source = "
+example Just = x
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_16() {
+    let source = "
+infixl 1 add as +
+
+example = a +
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_17() {
+    let source = "
+example = a)
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_18() {
+    let source = "
+infixr 9 type Function as ->
+
+example : Maybe a -> b -> c
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_19() {
+    let source = "
+example = \\x -> x
+
+example = \\_ y z -> f z y
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_20() {
+    let source = "
+infixl 1 add as +
+
+example = map \\x -> x + 1
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_21() {
+    let source = "
+infixl 1 add as +
+
+example = let x = 1 in x + 2
+
+example = let x = 1 in let y = x in x + y
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_22() {
+    let source = "
+example = []
+
+example = [1]
+
+example = [1, f x, [2]]
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_23() {
+    let source = "
+example = {}
+
+example = { a = 1 }
+
+example = { a = 1, b = { c = f x } }
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_24() {
+    let source = "
+example = [1, 2,]
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_25() {
+    let source = "
+infixl 99999999999999999999 add as +
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+
+    let source = "
+infixl 255 add as +
+
+f = a + b
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_26() {
+    let source = "
+infixl 5 Data.Semiring.add as +
+infixr 9 type Data.Function.Function as ->
+
+example : Data.Maybe.Maybe a -> Effect.Effect Unit
+
+example = case Data.Maybe.Just List.empty of
+  Data.Maybe.Just x -> Data.List.map f x + 1
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_27() {
+    let source = "
+infixl 1 add as +
+infixl 2 mul as *
+
+example = map (+ 1) xs
+
+example = map (1 + 2 *) xs
+
+example = (a + b)
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_28() {
+    let source = "
+infixl 1 add as +
+
+example as = as + 1
+
+example = case x of
+  as -> \\as -> as
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn top_level_31() {
+    let source = "
+infixr 9 type Function as ->
+
+identity : forall a. a -> a
+
+show : Show a => a -> String
+
+compare : forall a b. Eq a => Eq b => a -> (forall c. c -> b) -> Boolean
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn undeclared_type_operator() {
+    let source = "example : a -> b -> c";
+    assert_eq!(
+        parse_error(source),
+        ParseError::UnknownBindingPower("->".into())
+    );
+}
+
+fn top_level_operator(module: &Module) -> &str {
+    match &module.declarations[0].kind {
+        DeclarationK::ValueDeclaration(_, _, expression) => match &expression.kind {
+            ExpressionK::BinaryOperator(_, operator, _) => operator,
+            kind => panic!("Expected a binary operator, got {:?}", kind),
+        },
+        kind => panic!("Expected a value declaration, got {:?}", kind),
+    }
+}
+
+fn top_level_expression(source: &str) -> anyhow::Result<ExpressionK> {
+    let source = format!(
+        "infixl 1 add as +\ninfixl 1 sub as -\n\nexample = {}",
+        source
+    );
+    let mut module = parse_top_level(&source)?;
+    match module.declarations.remove(0).kind {
+        DeclarationK::ValueDeclaration(_, _, expression) => Ok(expression.kind),
+        kind => panic!("Expected a value declaration, got {:?}", kind),
+    }
+}
+
+#[test]
+fn sections() {
+    let section = |source| match top_level_expression(source).unwrap() {
+        ExpressionK::LeftSection(_, operator) => format!("left {}", operator),
+        ExpressionK::RightSection(operator, _) => format!("right {}", operator),
+        kind => format!("{:?}", kind),
+    };
+    assert_eq!(section("(1 +)"), "left +");
+    assert_eq!(section("(+ 1)"), "right +");
+    assert_eq!(section("(1 -)"), "left -");
+    assert_eq!(section("(- 1)"), "right -");
+
+    match top_level_expression("(a + b)").unwrap() {
+        ExpressionK::Parenthesized(expression) => assert!(matches!(
+            expression.kind,
+            ExpressionK::BinaryOperator(_, operator, _) if operator == "+"
+        )),
+        kind => panic!("Expected a parenthesized expression, got {:?}", kind),
+    }
+
+    let negative = |source| match top_level_expression(source).unwrap() {
+        ExpressionK::Parenthesized(expression) => expression.to_string(),
+        kind => panic!("Expected a parenthesized expression, got {:?}", kind),
+    };
+    assert_eq!(negative("(-1)"), "-1");
+    assert_eq!(negative("(-1.5)"), "-1.5");
+    assert_eq!(negative("(a -1)"), "a - 1");
+}
+
+#[test]
+fn fixity_tables() {
+    let source = "example = a + b * c";
+
+    let additive_first = parse_fixities("infixl 6 add as +\ninfixl 7 mul as *").unwrap();
+    let module = parse_top_level_with(source, additive_first).unwrap();
+    assert_eq!(top_level_operator(&module), "+");
+
+    let multiplicative_first = parse_fixities("infixl 7 add as +\ninfixl 6 mul as *").unwrap();
+    let module = parse_top_level_with(source, multiplicative_first).unwrap();
+    assert_eq!(top_level_operator(&module), "*");
+
+    let declared = "infixl 6 add as +\ninfixl 7 mul as *\n\nexample = a + b * c";
+    let module = parse_top_level_with(declared, parse_fixities("infixl 8 add as +").unwrap());
+    assert_eq!(top_level_operator(&module.unwrap()), "+");
+}
+
+#[test]
+fn top_level_29() {
+    let source = "
+infixl 6 add as +
+
+example = a <> b <> c + d
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn recover_between_declarations() {
+    let source = "
+infixl 1 add as +
+
+example = (a +
+
+example = f x + 1
+
+example = )
+
+infixl 256 sub as -
+";
+    let (module, errors) = parse_top_level_recovering(source, Default::default());
+    let rendered: Vec<String> = module
+        .declarations
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(rendered, vec!["example = f x + 1"]);
+    let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        errors,
+        vec![
+            "Integer literal at offset 75 is out of range.",
+            "Unexpected layout separator at offset 36.",
+            "Unexpected `)` at offset 65.",
+        ]
+    );
+}
+
+#[test]
+fn top_level_30() {
+    let source = "
+example = case f true of
+  true, false -> false
+  _, _ -> g [true, x]
+";
+    insta::assert_debug_snapshot!(parse_top_level(source));
+}
+
+#[test]
+fn error_after_comment() {
+    let source = "example = f {- a comment -} )";
+    assert_eq!(
+        parse_error(source),
+        ParseError::UnexpectedToken(
+            Token {
+                comment_begin: 11,
+                comment_end: 28,
+                begin: 28,
+                end: 29,
+                kind: TokenK::CloseDelimiter(DelimiterK::Round),
+                depth: 0,
+            },
+            vec![TokenK::Layout(LayoutK::Separator)]
+        )
+    );
+}
+
+#[test]
+fn display_0() {
+    let source = "
+infixl 1 add as +
+infixl 1 sub as -
+infixl 2 mul as *
+
+example x = f (g x) + 1
+
+example = { a = [1, 2], b = {} }
+
+example = (+ 1) (2 * 3 +) (4 - 5)
+
+example = case a, b of
+  Just (Left x), _ if x -> do
+    y <- pure x
+    let
+      z = y
+    pure z
+  _, _ -> if a then b else c
+";
+    let module = parse_top_level(source).unwrap();
+    let rendered: Vec<String> = module
+        .declarations
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        rendered,
+        vec![
+            "example x = f (g x) + 1",
+            "example = { a = [1, 2], b = {} }",
+            "example = (+ 1) (2 * 3 +) (4 - 5)",
+            "example = case a, b of { Just (Left x), _ if x -> do { y <- pure x; let { z = y }; pure z }; _, _ -> if a then b else c }",
+        ]
+    );
+}
+
+fn parse_error(source: &str) -> ParseError {
+    parse_top_level(source).unwrap_err().downcast().unwrap()
+}
+
+#[test]
+fn parse_error_kinds() {
+    match parse_error("example = a +") {
+        ParseError::UnexpectedToken(token, _) => {
+            assert_eq!(token.kind, TokenK::Layout(LayoutK::Separator));
+            assert_eq!(token.begin, 13);
+        }
+        error => panic!("Expected an unexpected token, got {:?}", error),
+    }
+    match parse_error("example = )") {
+        ParseError::UnexpectedToken(token, _) => {
+            assert_eq!(token.kind, TokenK::CloseDelimiter(DelimiterK::Round));
+            assert_eq!((token.begin, token.end), (10, 11));
+        }
+        error => panic!("Expected an unexpected token, got {:?}", error),
+    }
+}
+
+#[test]
+fn expected_tokens() {
+    assert_eq!(
+        parse_error("example = case x y").to_string(),
+        "Unexpected layout separator at offset 18, expected `,` or keyword `of`."
+    );
+    assert_eq!(
+        parse_error("infixl 1 add + sub").to_string(),
+        "Unexpected operator at offset 13, expected keyword `as`."
+    );
+    assert_eq!(
+        parse_error("infixl 1 Add as +").to_string(),
+        "Unexpected upper-case identifier at offset 9, \
+         expected identifier or qualified identifier."
+    );
+}
+
+#[test]
+fn recover_within_blocks() {
+    let source = "
+example = case x of
+  Just y -> )
+  Nothing -> do
+    log ]
+    pure 1
+  _ -> 2
+
+example = case x of
+  _ -> )
+
+example = f x
+";
+    let (module, errors) = parse_top_level_recovering(source, Default::default());
+    let rendered: Vec<String> = module
+        .declarations
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        rendered,
+        vec![
+            "example = case x of { Nothing -> do { pure 1 }; _ -> 2 }",
+            "example = f x",
+        ]
+    );
+    let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        errors,
+        vec![
+            "Unexpected `)` at offset 33.",
+            "Unexpected identifier at offset 55.",
+            "Unexpected `)` at offset 109.",
+        ]
+    );
+}
+
+#[test]
+fn integer_literal_overflow() {
+    assert_eq!(
+        parse_error("f = 99999999999999999999999"),
+        ParseError::IntegerOverflow(4)
+    );
+    assert_eq!(
+        parse_error("f = g (-99999999999999999999999)"),
+        ParseError::IntegerOverflow(8)
+    );
+    assert_eq!(
+        parse_error("f = case x of\n  99999999999999999999999 -> 1"),
+        ParseError::IntegerOverflow(16)
+    );
+    assert!(parse_top_level("f = 18446744073709551615").is_ok());
+}
+
+#[test]
+fn type_keywords_outside_types() {
+    let module = parse_top_level("infixl 1 implies as =>\n\nforall = forall => as").unwrap();
+    assert_eq!(module.declarations[0].to_string(), "forall = forall => as");
+    let module = parse_top_level("example : forall a. Show a => a").unwrap();
+    assert_eq!(
+        module.declarations[0].to_string(),
+        "example : forall a. Show a => a"
+    );
+}